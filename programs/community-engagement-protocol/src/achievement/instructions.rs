@@ -1,15 +1,30 @@
 use super::state::*;
+use crate::attester::state::{authorize_award, Attester};
 use crate::brand::state::Brand;
+use crate::bubblegum::{
+    self, Bubblegum, MetadataArgs, ACCOUNT_COMPRESSION_PROGRAM_ID, BUBBLEGUM_PROGRAM_ID,
+    NOOP_PROGRAM_ID,
+};
 use crate::errors::CepError;
+use crate::points::state::{credit_award_points, UserPoints};
+use crate::season::state::{Season, SeasonCredit};
+use crate::token_extensions::{is_token_2022, NewMint, TokenMetadataArgs};
+use crate::utils::{create_program_account, load_or_create_account, store_account};
 use crate::ProgramState;
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    associated_token::AssociatedToken,
+    associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken},
     metadata::{
-        mint_new_edition_from_master_edition_via_token, Metadata,
-        MintNewEditionFromMasterEditionViaToken,
+        freeze_delegated_account, mint_new_edition_from_master_edition_via_token,
+        thaw_delegated_account, FreezeDelegatedAccount, Metadata,
+        MintNewEditionFromMasterEditionViaToken, ThawDelegatedAccount,
+    },
+    token::{self, Mint, Token, TokenAccount},
+    token_interface::{
+        self, approve, burn, freeze_account, get_mint_extension_data, mint_to,
+        spl_token_2022::extension::permanent_delegate::PermanentDelegate, thaw_account, Approve,
+        Burn, FreezeAccount, MintTo, ThawAccount, TokenInterface,
     },
-    token::{self, mint_to, Mint, MintTo, Token, TokenAccount},
 };
 
 pub fn create_achievement(
@@ -29,6 +44,10 @@ pub fn create_achievement(
     if description.chars().count() > 200 {
         return Err(CepError::DescriptionTooLong.into());
     }
+    require!(
+        criteria.len() <= Achievement::MAX_CRITERIA_LENGTH,
+        CepError::CriteriaTooLong
+    );
 
     achievement.brand = brand.key();
     achievement.name = name;
@@ -37,6 +56,8 @@ pub fn create_achievement(
     achievement.points = points;
     achievement.created_at = clock.unix_timestamp;
     achievement.updated_at = clock.unix_timestamp;
+    achievement.archived = false;
+    achievement.soulbound = false;
 
     brand.achievements.push(achievement.key());
 
@@ -48,6 +69,7 @@ pub fn create_achievement(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn create_fungible_achievement(
     ctx: Context<CreateFungibleAchievement>,
     name: String,
@@ -55,6 +77,8 @@ pub fn create_fungible_achievement(
     criteria: String,
     points: u32,
     supply: u64,
+    soulbound: bool,
+    token_metadata: Option<TokenMetadataArgs>,
 ) -> Result<()> {
     let achievement = &mut ctx.accounts.achievement;
     let brand = &mut ctx.accounts.brand;
@@ -66,6 +90,31 @@ pub fn create_fungible_achievement(
     if description.chars().count() > 200 {
         return Err(CepError::DescriptionTooLong.into());
     }
+    require!(
+        criteria.len() <= Achievement::MAX_CRITERIA_LENGTH,
+        CepError::CriteriaTooLong
+    );
+
+    // Token-2022 badges carry their metadata on the mint and are made
+    // soulbound with the NonTransferable extension instead of being frozen
+    let token_program = ctx.accounts.token_program.to_account_info();
+    NewMint {
+        token_program: &token_program,
+        system_program: &ctx.accounts.system_program.to_account_info(),
+        payer: &ctx.accounts.tronic_admin.to_account_info(),
+        mint: &ctx.accounts.token_mint.to_account_info(),
+        authority: &ctx.accounts.mint_authority.to_account_info(),
+        signer_seeds: &[&[
+            MINT_AUTHORITY_SEED,
+            brand.key().as_ref(),
+            &[ctx.bumps.mint_authority],
+        ]],
+    }
+    .create(
+        0,
+        token_metadata.map(|args| (name.clone(), args)),
+        (soulbound && is_token_2022(&token_program)).then(|| ctx.accounts.mint_authority.key()),
+    )?;
 
     achievement.brand = brand.key();
     achievement.name = name;
@@ -77,11 +126,11 @@ pub fn create_fungible_achievement(
     achievement.achievement_type = AchievementType::Fungible;
     achievement.token_mint = Some(ctx.accounts.token_mint.key());
     achievement.token_supply = Some(supply);
+    achievement.archived = false;
+    achievement.soulbound = soulbound;
 
     brand.achievements.push(achievement.key());
 
-    // The mint is now initialized automatically by Anchor
-
     msg!(
         "Fungible Achievement '{}' created for Brand '{}'",
         achievement.name,
@@ -110,6 +159,15 @@ pub struct CreateNonFungibleAchievement<'info> {
             + 32 // Token Mint
             + 8 // Token Supply
             + 200 // Metadata URI
+            + 1 // Archived
+            + 1 // Soulbound
+            + 33 // Merkle Tree
+            + 4 + ThresholdRule::SPACE * Achievement::MAX_RULES // Rules
+            + 4 + 32 * Achievement::MAX_PREREQUISITES // Prerequisites
+            + 1 + 8 // Available From
+            + 1 + 8 // Available Until
+            + 1 + 8 // Max Awards
+            + 8 // Awarded Count
     )]
     pub achievement: Account<'info, Achievement>,
 
@@ -144,12 +202,17 @@ pub fn create_non_fungible_achievement(
     criteria: String,
     points: u32,
     metadata_uri: String,
+    soulbound: bool,
 ) -> Result<()> {
     msg!("Creating non-fungible achievement: {}", name);
 
     // Validate inputs
     require!(name.len() <= 100, CepError::NameTooLong);
     require!(description.len() <= 200, CepError::DescriptionTooLong);
+    require!(
+        criteria.len() <= Achievement::MAX_CRITERIA_LENGTH,
+        CepError::CriteriaTooLong
+    );
     require!(metadata_uri.len() <= 200, CepError::UriTooLong);
 
     // Set achievement data
@@ -165,6 +228,8 @@ pub fn create_non_fungible_achievement(
     achievement.token_mint = Some(ctx.accounts.mint.key());
     achievement.token_supply = Some(0);
     achievement.metadata_uri = Some(metadata_uri.clone());
+    achievement.archived = false;
+    achievement.soulbound = soulbound;
 
     // Add achievement to brand
     ctx.accounts.brand.achievements.push(achievement.key());
@@ -173,16 +238,100 @@ pub fn create_non_fungible_achievement(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn create_compressed_achievement(
+    ctx: Context<CreateCompressedAchievement>,
+    name: String,
+    description: String,
+    criteria: String,
+    points: u32,
+    metadata_uri: String,
+    max_depth: u32,
+    max_buffer_size: u32,
+) -> Result<()> {
+    require!(
+        name.len() <= bubblegum::MAX_NAME_LENGTH,
+        CepError::NameTooLong
+    );
+    require!(description.len() <= 200, CepError::DescriptionTooLong);
+    require!(
+        criteria.len() <= Achievement::MAX_CRITERIA_LENGTH,
+        CepError::CriteriaTooLong
+    );
+    require!(metadata_uri.len() <= 200, CepError::UriTooLong);
+
+    let achievement_key = ctx.accounts.achievement.key();
+    let seeds = &[
+        b"tree-creator".as_ref(),
+        achievement_key.as_ref(),
+        &[ctx.bumps.tree_creator],
+    ];
+    ctx.accounts
+        .bubblegum()
+        .create_tree_config(max_depth, max_buffer_size, &[seeds])?;
+
+    let achievement = &mut ctx.accounts.achievement;
+    achievement.brand = ctx.accounts.brand.key();
+    achievement.name = name;
+    achievement.description = description;
+    achievement.criteria = criteria;
+    achievement.points = points;
+    achievement.created_at = Clock::get()?.unix_timestamp;
+    achievement.updated_at = achievement.created_at;
+    achievement.achievement_type = AchievementType::Compressed;
+    achievement.token_supply = Some(0);
+    achievement.metadata_uri = Some(metadata_uri);
+    achievement.archived = false;
+    achievement.soulbound = false;
+    achievement.merkle_tree = Some(ctx.accounts.merkle_tree.key());
+
+    ctx.accounts.brand.achievements.push(achievement_key);
+
+    msg!(
+        "Compressed achievement '{}' created on tree {}",
+        achievement.name,
+        ctx.accounts.merkle_tree.key()
+    );
+    Ok(())
+}
+
 pub fn award_fungible_achievement(ctx: Context<AwardFungibleAchievement>) -> Result<()> {
+    authorize_award(
+        &ctx.accounts.brand,
+        &ctx.accounts.achievement.key(),
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.program_state.tronic_admin,
+        ctx.accounts.attester.as_mut(),
+        1,
+    )?;
+
     let achievement = &mut ctx.accounts.achievement;
-    let user_achievement = &mut ctx.accounts.user_achievement;
     let clock = Clock::get()?;
 
-    // Record the achievement award
-    user_achievement.user = ctx.accounts.user.key();
-    user_achievement.achievement = achievement.key();
-    user_achievement.brand = achievement.brand;
-    user_achievement.awarded_at = clock.unix_timestamp;
+    require!(!achievement.archived, CepError::AchievementArchived);
+    require!(
+        achievement.has_prerequisites(&ctx.accounts.user.key(), ctx.remaining_accounts),
+        CepError::PrerequisiteMissing
+    );
+    achievement.record_awards(clock.unix_timestamp, 1)?;
+
+    // Record the achievement award; its address allows one per user
+    require!(
+        ctx.accounts.user_achievement.data_is_empty(),
+        CepError::AchievementAlreadyAwarded
+    );
+    create_user_achievement(
+        &ctx.accounts.user_achievement,
+        ctx.bumps.user_achievement,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+        &UserAchievement {
+            user: ctx.accounts.user.key(),
+            achievement: achievement.key(),
+            brand: achievement.brand,
+            awarded_at: clock.unix_timestamp,
+        },
+    )?;
 
     // Add the achievement to the user's list of achievements
     ctx.accounts
@@ -191,24 +340,356 @@ pub fn award_fungible_achievement(ctx: Context<AwardFungibleAchievement>) -> Res
         .push(achievement.key());
 
     // Mint one token to the user's associated token account
-    let token_mint = &ctx.accounts.token_mint;
-    let user_token_account = &ctx.accounts.user_token_account;
-    let token_program = &ctx.accounts.token_program;
+    mint_badge(
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.user_token_account.to_account_info(),
+        &ctx.accounts.mint_authority,
+        &[
+            MINT_AUTHORITY_SEED,
+            ctx.accounts.brand.key().as_ref(),
+            &[ctx.bumps.mint_authority],
+        ],
+        &ctx.accounts.authority,
+        achievement.soulbound,
+    )?;
 
-    mint_to(
-        CpiContext::new(
-            token_program.to_account_info(),
-            MintTo {
-                mint: token_mint.to_account_info(),
-                to: user_token_account.to_account_info(),
-                authority: ctx.accounts.authority.to_account_info(),
+    credit_award_points(
+        achievement.brand,
+        ctx.accounts.user.key(),
+        achievement.points.into(),
+        clock.unix_timestamp,
+        &mut ctx.accounts.user_points,
+        &ctx.accounts.leaderboard,
+        SeasonCredit::new(
+            ctx.accounts.season.as_mut(),
+            ctx.accounts.user_season_points.as_deref(),
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?,
+    )?;
+
+    msg!(
+        "Fungible Achievement '{}' awarded to user {}",
+        achievement.name,
+        ctx.accounts.user.key()
+    );
+    Ok(())
+}
+
+/// Users per batch award. At five accounts each, or six with a season, a
+/// full batch stays under the 64 accounts a transaction can lock; it still
+/// needs a v0 transaction with a lookup table and a raised compute limit.
+/// Achievements with prerequisites fit fewer users.
+pub const MAX_BATCH_AWARD_SIZE: usize = 8;
+const BATCH_AWARD_ACCOUNTS_PER_USER: usize = 5;
+
+/// Awards a fungible achievement to every (user, user_achievements,
+/// user_token_account, user_achievement PDA, user_points PDA) tuple in
+/// `remaining_accounts`. With a `season`, each tuple continues with the
+/// user's season points PDA, then the user's records for the achievement's
+/// prerequisites.
+/// Tuples that fail validation or were already awarded are skipped; the
+/// returned flags report which users received the award. The whole batch
+/// fails if the achievement is closed or the awards exceed its cap.
+pub fn batch_award_fungible_achievement<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchAwardFungibleAchievement<'info>>,
+) -> Result<Vec<bool>> {
+    let remaining = ctx.remaining_accounts;
+
+    require!(
+        !ctx.accounts.achievement.archived,
+        CepError::AchievementArchived
+    );
+    let accounts_per_user = BATCH_AWARD_ACCOUNTS_PER_USER
+        + usize::from(ctx.accounts.season.is_some())
+        + ctx.accounts.achievement.prerequisites.len();
+    let users = remaining.len() / accounts_per_user;
+    let leftover = remaining.len() % accounts_per_user;
+    require!(leftover == 0, CepError::InvalidBatchAccounts);
+    require!(users <= MAX_BATCH_AWARD_SIZE, CepError::BatchTooLarge);
+
+    let clock = Clock::get()?;
+    let mut results = Vec::with_capacity(users);
+    for accounts in remaining.chunks(accounts_per_user) {
+        let awarded =
+            ctx.accounts
+                .award(accounts, ctx.bumps.mint_authority, clock.unix_timestamp)?;
+        if !awarded {
+            msg!("Skipped user {}", accounts[0].key());
+        }
+        results.push(awarded);
+    }
+
+    // Attesters are charged only for the users actually awarded
+    let awarded = results.iter().filter(|awarded| **awarded).count();
+    ctx.accounts
+        .achievement
+        .record_awards(clock.unix_timestamp, awarded as u64)?;
+    authorize_award(
+        &ctx.accounts.brand,
+        &ctx.accounts.achievement.key(),
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.program_state.tronic_admin,
+        ctx.accounts.attester.as_mut(),
+        awarded as u32,
+    )?;
+
+    msg!(
+        "Fungible Achievement '{}' awarded to {} of {} users",
+        ctx.accounts.achievement.name,
+        awarded,
+        results.len()
+    );
+    Ok(results)
+}
+
+impl<'info> BatchAwardFungibleAchievement<'info> {
+    /// Awards one tuple, returning `false` instead of failing the whole
+    /// batch when its accounts don't check out.
+    fn award(
+        &mut self,
+        accounts: &'info [AccountInfo<'info>],
+        mint_authority_bump: u8,
+        now: i64,
+    ) -> Result<bool> {
+        let [user, user_achievements_info, user_token_account, user_achievement, user_points_info, rest @ ..] =
+            accounts
+        else {
+            return Ok(false);
+        };
+        let (user_season_points, prerequisite_records) = match &self.season {
+            Some(_) => match rest.split_first() {
+                Some((user_season_points, records)) => (Some(user_season_points), records),
+                None => return Ok(false),
             },
-        ),
+            None => (None, rest),
+        };
+        let achievement_key = self.achievement.key();
+        let brand_key = self.brand.key();
+
+        let (expected_points, _) = Pubkey::find_program_address(
+            &[b"user-points", brand_key.as_ref(), user.key().as_ref()],
+            &crate::ID,
+        );
+        if user_points_info.key() != expected_points || !user_points_info.is_writable {
+            return Ok(false);
+        }
+        if let (Some(season), Some(user_season_points)) = (&self.season, user_season_points) {
+            let (expected_season_points, _) = Pubkey::find_program_address(
+                &[
+                    b"user-season-points",
+                    season.key().as_ref(),
+                    user.key().as_ref(),
+                ],
+                &crate::ID,
+            );
+            if user_season_points.key() != expected_season_points || !user_season_points.is_writable
+            {
+                return Ok(false);
+            }
+        }
+
+        if !self
+            .achievement
+            .has_prerequisites(user.key, prerequisite_records)
+        {
+            return Ok(false);
+        }
+
+        // One award per user, enforced by the record's address
+        let (expected_record, bump) = Pubkey::find_program_address(
+            &[
+                b"user-achievement",
+                achievement_key.as_ref(),
+                user.key().as_ref(),
+            ],
+            &crate::ID,
+        );
+        if user_achievement.key() != expected_record
+            || !user_achievement.is_writable
+            || !user_achievement.data_is_empty()
+        {
+            return Ok(false);
+        }
+
+        let expected_token_account = get_associated_token_address_with_program_id(
+            user.key,
+            &self.token_mint.key(),
+            self.token_program.key,
+        );
+        if user_token_account.key() != expected_token_account || !user_token_account.is_writable {
+            return Ok(false);
+        }
+
+        if !user_achievements_info.is_writable {
+            return Ok(false);
+        }
+        let Ok(mut user_achievements) =
+            Account::<UserAchievements>::try_from(user_achievements_info)
+        else {
+            return Ok(false);
+        };
+        if user_achievements.user != user.key() {
+            return Ok(false);
+        }
+
+        // Record the achievement award
+        create_user_achievement(
+            user_achievement,
+            bump,
+            &self.authority,
+            &self.system_program,
+            &UserAchievement {
+                user: user.key(),
+                achievement: achievement_key,
+                brand: self.achievement.brand,
+                awarded_at: now,
+            },
+        )?;
+
+        user_achievements.achievements.push(achievement_key);
+        user_achievements.exit(&crate::ID)?;
+
+        create_badge_account_if_missing(
+            user_token_account,
+            user,
+            &self.token_mint,
+            &self.authority,
+            &self.token_program,
+            &self.associated_token_program,
+            &self.system_program,
+        )?;
+        mint_badge(
+            &self.token_program,
+            &self.token_mint,
+            user_token_account,
+            &self.mint_authority,
+            &[
+                MINT_AUTHORITY_SEED,
+                brand_key.as_ref(),
+                &[mint_authority_bump],
+            ],
+            &self.authority,
+            self.achievement.soulbound,
+        )?;
+
+        let mut user_points: UserPoints = load_or_create_account(
+            user_points_info,
+            &[b"user-points", brand_key.as_ref(), user.key().as_ref()],
+            UserPoints::SPACE,
+            &self.authority,
+            &self.system_program,
+        )?;
+        credit_award_points(
+            brand_key,
+            user.key(),
+            self.achievement.points.into(),
+            now,
+            &mut user_points,
+            &self.leaderboard,
+            SeasonCredit::new(
+                self.season.as_mut(),
+                user_season_points,
+                &self.authority,
+                &self.system_program,
+            )?,
+        )?;
+        store_account(user_points_info, &user_points)?;
+
+        Ok(true)
+    }
+}
+
+pub fn award_compressed_achievement(ctx: Context<AwardCompressedAchievement>) -> Result<()> {
+    authorize_award(
+        &ctx.accounts.brand,
+        &ctx.accounts.achievement.key(),
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.program_state.tronic_admin,
+        ctx.accounts.attester.as_mut(),
         1,
     )?;
 
+    let clock = Clock::get()?;
+
+    require!(
+        !ctx.accounts.achievement.archived,
+        CepError::AchievementArchived
+    );
+    require!(
+        ctx.accounts
+            .achievement
+            .has_prerequisites(&ctx.accounts.user.key(), ctx.remaining_accounts),
+        CepError::PrerequisiteMissing
+    );
+    ctx.accounts
+        .achievement
+        .record_awards(clock.unix_timestamp, 1)?;
+
+    let achievement_key = ctx.accounts.achievement.key();
+    let metadata = MetadataArgs::new(
+        ctx.accounts.achievement.name.clone(),
+        ctx.accounts
+            .achievement
+            .metadata_uri
+            .clone()
+            .unwrap_or_default(),
+    );
+    let seeds = &[
+        b"tree-creator".as_ref(),
+        achievement_key.as_ref(),
+        &[ctx.bumps.tree_creator],
+    ];
+    ctx.accounts
+        .bubblegum()
+        .mint_v1(&ctx.accounts.user.to_account_info(), metadata, &[seeds])?;
+
+    // Record the achievement award; its address allows one per user
+    require!(
+        ctx.accounts.user_achievement.data_is_empty(),
+        CepError::AchievementAlreadyAwarded
+    );
+    create_user_achievement(
+        &ctx.accounts.user_achievement,
+        ctx.bumps.user_achievement,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+        &UserAchievement {
+            user: ctx.accounts.user.key(),
+            achievement: achievement_key,
+            brand: ctx.accounts.achievement.brand,
+            awarded_at: clock.unix_timestamp,
+        },
+    )?;
+
+    // Add the achievement to the user's list of achievements
+    ctx.accounts
+        .user_achievements
+        .achievements
+        .push(achievement_key);
+
+    // Leaves minted so far
+    let achievement = &mut ctx.accounts.achievement;
+    achievement.token_supply = Some(achievement.token_supply.unwrap_or_default() + 1);
+
+    credit_award_points(
+        achievement.brand,
+        ctx.accounts.user.key(),
+        achievement.points.into(),
+        clock.unix_timestamp,
+        &mut ctx.accounts.user_points,
+        &ctx.accounts.leaderboard,
+        SeasonCredit::new(
+            ctx.accounts.season.as_mut(),
+            ctx.accounts.user_season_points.as_deref(),
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?,
+    )?;
+
     msg!(
-        "Fungible Achievement '{}' awarded to user {}",
+        "Compressed Achievement '{}' awarded to user {}",
         achievement.name,
         ctx.accounts.user.key()
     );
@@ -216,15 +697,42 @@ pub fn award_fungible_achievement(ctx: Context<AwardFungibleAchievement>) -> Res
 }
 
 pub fn award_non_fungible_achievement(ctx: Context<AwardNonFungibleAchievement>) -> Result<()> {
+    authorize_award(
+        &ctx.accounts.brand,
+        &ctx.accounts.achievement.key(),
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.program_state.tronic_admin,
+        ctx.accounts.attester.as_mut(),
+        1,
+    )?;
+
     let achievement = &mut ctx.accounts.achievement;
-    let user_achievement = &mut ctx.accounts.user_achievement;
     let clock = Clock::get()?;
 
-    // Record the achievement award
-    user_achievement.user = ctx.accounts.user.key();
-    user_achievement.achievement = achievement.key();
-    user_achievement.brand = achievement.brand;
-    user_achievement.awarded_at = clock.unix_timestamp;
+    require!(!achievement.archived, CepError::AchievementArchived);
+    require!(
+        achievement.has_prerequisites(&ctx.accounts.user.key(), ctx.remaining_accounts),
+        CepError::PrerequisiteMissing
+    );
+    achievement.record_awards(clock.unix_timestamp, 1)?;
+
+    // Record the achievement award; its address allows one per user
+    require!(
+        ctx.accounts.user_achievement.data_is_empty(),
+        CepError::AchievementAlreadyAwarded
+    );
+    create_user_achievement(
+        &ctx.accounts.user_achievement,
+        ctx.bumps.user_achievement,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+        &UserAchievement {
+            user: ctx.accounts.user.key(),
+            achievement: achievement.key(),
+            brand: achievement.brand,
+            awarded_at: clock.unix_timestamp,
+        },
+    )?;
 
     // Add the achievement to the user's list of achievements
     ctx.accounts
@@ -261,6 +769,28 @@ pub fn award_non_fungible_achievement(ctx: Context<AwardNonFungibleAchievement>)
         edition_number,
     )?;
 
+    // The master edition is the mint's freeze authority, so soulbound NFTs
+    // are frozen and thawed through Token Metadata by the brand's mint
+    // authority as the holder's delegate
+    let soulbound = achievement.soulbound;
+    let brand_key = ctx.accounts.brand.key();
+    let mint_authority_seeds: &[&[u8]] = &[
+        MINT_AUTHORITY_SEED,
+        brand_key.as_ref(),
+        &[ctx.bumps.mint_authority],
+    ];
+    if soulbound && ctx.accounts.user_token_account.is_frozen() {
+        thaw_soulbound_edition(
+            &ctx.accounts.token_metadata_program,
+            &ctx.accounts.token_program,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_mint,
+            &ctx.accounts.master_edition,
+            &ctx.accounts.mint_authority,
+            mint_authority_seeds,
+        )?;
+    }
+
     // Transfer the new edition to the user
     token::transfer(
         CpiContext::new(
@@ -274,9 +804,39 @@ pub fn award_non_fungible_achievement(ctx: Context<AwardNonFungibleAchievement>)
         1,
     )?;
 
+    if soulbound {
+        ctx.accounts.user_token_account.reload()?;
+        freeze_soulbound_edition(
+            &ctx.accounts.token_metadata_program,
+            &ctx.accounts.token_program,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_mint,
+            &ctx.accounts.master_edition,
+            &ctx.accounts.user,
+            &ctx.accounts.mint_authority,
+            ctx.accounts.user_token_account.amount,
+            mint_authority_seeds,
+        )?;
+    }
+
     // Update the achievement's token supply
     achievement.token_supply = Some(edition_number);
 
+    credit_award_points(
+        achievement.brand,
+        ctx.accounts.user.key(),
+        achievement.points.into(),
+        clock.unix_timestamp,
+        &mut ctx.accounts.user_points,
+        &ctx.accounts.leaderboard,
+        SeasonCredit::new(
+            ctx.accounts.season.as_mut(),
+            ctx.accounts.user_season_points.as_deref(),
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?,
+    )?;
+
     msg!(
         "Non-Fungible Achievement '{}' awarded to user {}",
         achievement.name,
@@ -285,6 +845,404 @@ pub fn award_non_fungible_achievement(ctx: Context<AwardNonFungibleAchievement>)
     Ok(())
 }
 
+/// Thaws a soulbound token account and burns `amount` from it at the
+/// holder's request, which has to be the whole balance of a frozen account.
+/// Tokens frozen by the brand's mint authority, directly or through Token
+/// Metadata for NFTs, are thawed by the program; tokens frozen by another
+/// freeze authority need it to co-sign.
+pub fn thaw_and_burn(ctx: Context<ThawAndBurn>, amount: u64) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let brand_key = accounts.brand.key();
+
+    require_burn_amount(&accounts.token_account, amount)?;
+    if accounts.token_account.is_frozen() {
+        match &accounts.freeze_authority {
+            Some(freeze_authority) => {
+                require!(
+                    accounts.mint.freeze_authority == Some(freeze_authority.key()).into(),
+                    CepError::Unauthorized
+                );
+                thaw_token_account(
+                    &accounts.token_program,
+                    &accounts.token_account,
+                    &accounts.mint,
+                    freeze_authority,
+                    &[],
+                )?;
+            }
+            None => thaw_soulbound(
+                &accounts.token_program,
+                &accounts.token_account,
+                &accounts.mint,
+                &accounts.mint_authority,
+                &[
+                    MINT_AUTHORITY_SEED,
+                    brand_key.as_ref(),
+                    &[ctx.bumps.mint_authority],
+                ],
+                accounts.edition.as_ref(),
+                accounts.token_metadata_program.as_ref(),
+            )?,
+        }
+    }
+
+    burn(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            Burn {
+                mint: accounts.mint.to_account_info(),
+                from: accounts.token_account.to_account_info(),
+                authority: accounts.holder.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    msg!(
+        "Burned {} token(s) of mint {} held by {}",
+        amount,
+        accounts.mint.key(),
+        accounts.holder.key()
+    );
+    Ok(())
+}
+
+/// Burns `amount` of a soulbound token on the brand's behalf, without the
+/// holder, in full when the account is frozen. The brand's mint authority
+/// burns as a delegate: the one holders approve for soulbound NFTs, or the
+/// permanent delegate of non-transferable Token-2022 mints. Frozen SPL Token badges have no delegate, so only their
+/// holders can burn them.
+pub fn revoke_soulbound(ctx: Context<RevokeSoulbound>, amount: u64) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let brand_key = accounts.brand.key();
+    let mint_authority = accounts.mint_authority.key();
+    let mint_authority_seeds: &[&[u8]] = &[
+        MINT_AUTHORITY_SEED,
+        brand_key.as_ref(),
+        &[ctx.bumps.mint_authority],
+    ];
+
+    let delegated = accounts.token_account.delegate == Some(mint_authority).into();
+    let permanent_delegate = is_token_2022(&accounts.token_program.to_account_info())
+        && get_mint_extension_data::<PermanentDelegate>(&accounts.mint.to_account_info())
+            .is_ok_and(|extension| {
+                Option::<Pubkey>::from(extension.delegate) == Some(mint_authority)
+            });
+    require!(
+        delegated || permanent_delegate,
+        CepError::SoulboundNotRevocable
+    );
+
+    require_burn_amount(&accounts.token_account, amount)?;
+    if accounts.token_account.is_frozen() {
+        thaw_soulbound(
+            &accounts.token_program,
+            &accounts.token_account,
+            &accounts.mint,
+            &accounts.mint_authority,
+            mint_authority_seeds,
+            accounts.edition.as_ref(),
+            accounts.token_metadata_program.as_ref(),
+        )?;
+    }
+
+    burn(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            Burn {
+                mint: accounts.mint.to_account_info(),
+                from: accounts.token_account.to_account_info(),
+                authority: accounts.mint_authority.to_account_info(),
+            },
+            &[mint_authority_seeds],
+        ),
+        amount,
+    )?;
+
+    msg!(
+        "Revoked {} token(s) of mint {} held by {}",
+        amount,
+        accounts.mint.key(),
+        accounts.token_account.owner
+    );
+    Ok(())
+}
+
+/// Nothing is re-frozen after a burn, so frozen soulbound tokens can only be
+/// burned in full, or the rest of them would be left transferable.
+fn require_burn_amount(
+    token_account: &InterfaceAccount<token_interface::TokenAccount>,
+    amount: u64,
+) -> Result<()> {
+    require!(
+        amount > 0 && (!token_account.is_frozen() || amount == token_account.amount),
+        CepError::SoulboundPartialBurn
+    );
+    Ok(())
+}
+
+/// Thaws a token account frozen by the brand's mint authority, through Token
+/// Metadata when the mint's master edition holds its freeze authority.
+fn thaw_soulbound<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    token_account: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    mint: &InterfaceAccount<'info, token_interface::Mint>,
+    mint_authority: &UncheckedAccount<'info>,
+    mint_authority_seeds: &[&[u8]],
+    edition: Option<&UncheckedAccount<'info>>,
+    token_metadata_program: Option<&Program<'info, Metadata>>,
+) -> Result<()> {
+    if mint.freeze_authority == Some(mint_authority.key()).into() {
+        return thaw_token_account(
+            token_program,
+            token_account,
+            mint,
+            mint_authority,
+            &[mint_authority_seeds],
+        );
+    }
+
+    let (Some(edition), Some(token_metadata_program)) = (edition, token_metadata_program) else {
+        return Err(CepError::EditionAccountsRequired.into());
+    };
+    require!(
+        token_account.delegate == Some(mint_authority.key()).into(),
+        CepError::Unauthorized
+    );
+    thaw_soulbound_edition(
+        token_metadata_program,
+        token_program,
+        token_account,
+        mint,
+        edition,
+        mint_authority,
+        mint_authority_seeds,
+    )
+}
+
+/// Approves the brand's mint authority as delegate of `amount` soulbound NFT
+/// tokens, then has it freeze them through Token Metadata, since creating
+/// the master edition hands the mint's freeze authority to the edition.
+/// Only the holder can approve a delegate, so it has to sign.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn freeze_soulbound_edition<'info>(
+    token_metadata_program: &Program<'info, Metadata>,
+    token_program: &impl ToAccountInfo<'info>,
+    token_account: &impl ToAccountInfo<'info>,
+    mint: &impl ToAccountInfo<'info>,
+    edition: &impl ToAccountInfo<'info>,
+    holder: &AccountInfo<'info>,
+    mint_authority: &UncheckedAccount<'info>,
+    amount: u64,
+    mint_authority_seeds: &[&[u8]],
+) -> Result<()> {
+    require!(holder.is_signer, CepError::SoulboundHolderSignatureRequired);
+    approve(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Approve {
+                to: token_account.to_account_info(),
+                delegate: mint_authority.to_account_info(),
+                authority: holder.clone(),
+            },
+        ),
+        amount,
+    )?;
+    freeze_delegated_account(CpiContext::new_with_signer(
+        token_metadata_program.to_account_info(),
+        FreezeDelegatedAccount {
+            metadata: token_metadata_program.to_account_info(),
+            delegate: mint_authority.to_account_info(),
+            token_account: token_account.to_account_info(),
+            edition: edition.to_account_info(),
+            mint: mint.to_account_info(),
+            token_program: token_program.to_account_info(),
+        },
+        &[mint_authority_seeds],
+    ))
+}
+
+/// Thaws a soulbound NFT frozen by `freeze_soulbound_edition`.
+pub(crate) fn thaw_soulbound_edition<'info>(
+    token_metadata_program: &Program<'info, Metadata>,
+    token_program: &impl ToAccountInfo<'info>,
+    token_account: &impl ToAccountInfo<'info>,
+    mint: &impl ToAccountInfo<'info>,
+    edition: &impl ToAccountInfo<'info>,
+    mint_authority: &UncheckedAccount<'info>,
+    mint_authority_seeds: &[&[u8]],
+) -> Result<()> {
+    thaw_delegated_account(CpiContext::new_with_signer(
+        token_metadata_program.to_account_info(),
+        ThawDelegatedAccount {
+            metadata: token_metadata_program.to_account_info(),
+            delegate: mint_authority.to_account_info(),
+            token_account: token_account.to_account_info(),
+            edition: edition.to_account_info(),
+            mint: mint.to_account_info(),
+            token_program: token_program.to_account_info(),
+        },
+        &[mint_authority_seeds],
+    ))
+}
+
+pub(crate) fn freeze_token_account<'info>(
+    token_program: &impl ToAccountInfo<'info>,
+    token_account: &impl ToAccountInfo<'info>,
+    mint: &impl ToAccountInfo<'info>,
+    freeze_authority: &impl ToAccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    freeze_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        FreezeAccount {
+            account: token_account.to_account_info(),
+            mint: mint.to_account_info(),
+            authority: freeze_authority.to_account_info(),
+        },
+        signer_seeds,
+    ))
+}
+
+pub(crate) fn thaw_token_account<'info>(
+    token_program: &impl ToAccountInfo<'info>,
+    token_account: &impl ToAccountInfo<'info>,
+    mint: &impl ToAccountInfo<'info>,
+    freeze_authority: &impl ToAccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    thaw_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        ThawAccount {
+            account: token_account.to_account_info(),
+            mint: mint.to_account_info(),
+            authority: freeze_authority.to_account_info(),
+        },
+        signer_seeds,
+    ))
+}
+
+/// Creates the `[b"user-achievement", achievement, user]` record for award
+/// paths that can't use an `init` constraint.
+pub(crate) fn create_user_achievement<'info>(
+    record: &AccountInfo<'info>,
+    bump: u8,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    user_achievement: &UserAchievement,
+) -> Result<()> {
+    create_program_account(
+        record,
+        &[
+            b"user-achievement",
+            user_achievement.achievement.as_ref(),
+            user_achievement.user.as_ref(),
+            &[bump],
+        ],
+        8 + 32 + 32 + 32 + 8,
+        &payer.to_account_info(),
+        &system_program.to_account_info(),
+    )?;
+    user_achievement.try_serialize(&mut &mut record.try_borrow_mut_data()?[..])
+}
+
+/// Creates the user's associated token account for a badge mint unless it
+/// already exists.
+pub(crate) fn create_badge_account_if_missing<'info>(
+    token_account: &AccountInfo<'info>,
+    user: &AccountInfo<'info>,
+    token_mint: &InterfaceAccount<'info, token_interface::Mint>,
+    payer: &Signer<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    associated_token_program: &Program<'info, AssociatedToken>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    if !token_account.data_is_empty() {
+        return Ok(());
+    }
+    associated_token::create(CpiContext::new(
+        associated_token_program.to_account_info(),
+        associated_token::Create {
+            payer: payer.to_account_info(),
+            associated_token: token_account.clone(),
+            authority: user.clone(),
+            mint: token_mint.to_account_info(),
+            system_program: system_program.to_account_info(),
+            token_program: token_program.to_account_info(),
+        },
+    ))
+}
+
+/// Mints one badge token to `token_account`, signing with the brand's mint
+/// authority PDA, whose seeds and bump are `mint_authority_seeds`, when it
+/// owns the mint.
+pub(crate) fn mint_badge<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    token_mint: &InterfaceAccount<'info, token_interface::Mint>,
+    token_account: &AccountInfo<'info>,
+    mint_authority: &UncheckedAccount<'info>,
+    mint_authority_seeds: &[&[u8]],
+    authority: &Signer<'info>,
+    soulbound: bool,
+) -> Result<()> {
+    let authority = badge_authority(token_mint, mint_authority, authority);
+    let signer_seeds = &[mint_authority_seeds];
+
+    // Token-2022 soulbound badges are non-transferable mints; SPL Token ones
+    // are frozen, so the account is thawed to receive another token
+    let freezes = soulbound && !is_token_2022(&token_program.to_account_info());
+    let is_frozen =
+        token_interface::TokenAccount::try_deserialize(&mut &token_account.try_borrow_data()?[..])?
+            .is_frozen();
+    if freezes && is_frozen {
+        thaw_token_account(
+            token_program,
+            token_account,
+            token_mint,
+            &authority,
+            signer_seeds,
+        )?;
+    }
+
+    mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint: token_mint.to_account_info(),
+                to: token_account.clone(),
+                authority: authority.clone(),
+            },
+            signer_seeds,
+        ),
+        1,
+    )?;
+
+    if freezes {
+        freeze_token_account(
+            token_program,
+            token_account,
+            token_mint,
+            &authority,
+            signer_seeds,
+        )?;
+    }
+    Ok(())
+}
+
+/// Badges created before the mint authority PDA still have the Tronic admin
+/// as mint and freeze authority, which then has to sign the award itself.
+fn badge_authority<'info>(
+    mint: &InterfaceAccount<'info, token_interface::Mint>,
+    mint_authority: &UncheckedAccount<'info>,
+    authority: &Signer<'info>,
+) -> AccountInfo<'info> {
+    if mint.mint_authority == Some(mint_authority.key()).into() {
+        mint_authority.to_account_info()
+    } else {
+        authority.to_account_info()
+    }
+}
+
 pub fn initialize_user_achievements(ctx: Context<InitializeUserAchievements>) -> Result<()> {
     let user_achievements = &mut ctx.accounts.user_achievements;
     user_achievements.user = ctx.accounts.user.key();
@@ -302,6 +1260,11 @@ pub fn get_achievement_info(ctx: Context<GetAchievementInfo>) -> Result<Achievem
         brand: achievement.brand,
         created_at: achievement.created_at,
         updated_at: achievement.updated_at,
+        archived: achievement.archived,
+        available_from: achievement.available_from,
+        available_until: achievement.available_until,
+        max_awards: achievement.max_awards,
+        awarded_count: achievement.awarded_count,
     })
 }
 
@@ -310,16 +1273,195 @@ pub fn list_user_achievements(ctx: Context<ListUserAchievements>) -> Result<Vec<
     Ok(user_achievements.achievements.clone())
 }
 
+pub fn update_achievement(
+    ctx: Context<UpdateAchievement>,
+    name: String,
+    description: String,
+    criteria: String,
+    points: u32,
+    metadata_uri: Option<String>,
+) -> Result<()> {
+    let achievement = &mut ctx.accounts.achievement;
+    let clock = Clock::get()?;
+
+    if name.chars().count() > 50 {
+        return Err(CepError::NameTooLong.into());
+    }
+    if description.chars().count() > 200 {
+        return Err(CepError::DescriptionTooLong.into());
+    }
+    require!(
+        criteria.len() <= Achievement::MAX_CRITERIA_LENGTH,
+        CepError::CriteriaTooLong
+    );
+    // Compressed badges carry the name in their leaf metadata
+    if achievement.achievement_type == AchievementType::Compressed {
+        require!(
+            name.len() <= bubblegum::MAX_NAME_LENGTH,
+            CepError::NameTooLong
+        );
+    }
+    if let Some(uri) = &metadata_uri {
+        require!(uri.chars().count() <= 200, CepError::UriTooLong);
+        // Only non-fungible and compressed achievements carry a metadata URI
+        require!(
+            achievement.metadata_uri.is_some(),
+            CepError::InvalidAchievementType
+        );
+    }
+
+    achievement.name = name;
+    achievement.description = description;
+    achievement.criteria = criteria;
+    achievement.points = points;
+    if metadata_uri.is_some() {
+        achievement.metadata_uri = metadata_uri;
+    }
+    achievement.updated_at = clock.unix_timestamp;
+
+    msg!("Achievement '{}' updated", achievement.name);
+    Ok(())
+}
+
+/// Replaces the threshold rules `record_progress` checks before awarding.
+/// Only fungible achievements can be unlocked this way.
+pub fn set_achievement_rules(
+    ctx: Context<UpdateAchievement>,
+    rules: Vec<ThresholdRule>,
+) -> Result<()> {
+    let achievement = &mut ctx.accounts.achievement;
+    let clock = Clock::get()?;
+
+    require!(
+        rules.len() <= Achievement::MAX_RULES,
+        CepError::TooManyRules
+    );
+    for (index, rule) in rules.iter().enumerate() {
+        require!(
+            !rule.metric.is_empty() && rule.metric.len() <= ThresholdRule::MAX_METRIC_LENGTH,
+            CepError::InvalidMetric
+        );
+        require!(
+            rules[..index]
+                .iter()
+                .all(|other| other.metric != rule.metric),
+            CepError::InvalidMetric
+        );
+    }
+    if !rules.is_empty() {
+        require!(
+            achievement.achievement_type == AchievementType::Fungible,
+            CepError::InvalidAchievementType
+        );
+    }
+
+    achievement.rules = rules;
+    achievement.updated_at = clock.unix_timestamp;
+
+    msg!(
+        "Achievement '{}' now has {} rules",
+        achievement.name,
+        achievement.rules.len()
+    );
+    Ok(())
+}
+
+/// Replaces the achievements a user must hold before this one can be
+/// awarded, e.g. a silver badge requiring the bronze one.
+pub fn set_achievement_prerequisites(
+    ctx: Context<UpdateAchievement>,
+    prerequisites: Vec<Pubkey>,
+) -> Result<()> {
+    let achievement = &mut ctx.accounts.achievement;
+    let clock = Clock::get()?;
+
+    require!(
+        prerequisites.len() <= Achievement::MAX_PREREQUISITES,
+        CepError::TooManyPrerequisites
+    );
+    require!(
+        !prerequisites.contains(&achievement.key()),
+        CepError::InvalidPrerequisite
+    );
+
+    achievement.prerequisites = prerequisites;
+    achievement.updated_at = clock.unix_timestamp;
+
+    msg!(
+        "Achievement '{}' now has {} prerequisites",
+        achievement.name,
+        achievement.prerequisites.len()
+    );
+    Ok(())
+}
+
+/// Limits when and how many times the achievement can be awarded; `None`
+/// leaves that side open.
+pub fn set_achievement_availability(
+    ctx: Context<UpdateAchievement>,
+    available_from: Option<i64>,
+    available_until: Option<i64>,
+    max_awards: Option<u64>,
+) -> Result<()> {
+    let achievement = &mut ctx.accounts.achievement;
+    let clock = Clock::get()?;
+
+    if let (Some(available_from), Some(available_until)) = (available_from, available_until) {
+        require!(available_from < available_until, CepError::InvalidSchedule);
+    }
+
+    achievement.available_from = available_from;
+    achievement.available_until = available_until;
+    achievement.max_awards = max_awards;
+    achievement.updated_at = clock.unix_timestamp;
+
+    msg!("Achievement '{}' availability updated", achievement.name);
+    Ok(())
+}
+
+pub fn archive_achievement(ctx: Context<UpdateAchievement>) -> Result<()> {
+    let achievement = &mut ctx.accounts.achievement;
+    let clock = Clock::get()?;
+
+    achievement.archived = true;
+    achievement.updated_at = clock.unix_timestamp;
+
+    msg!("Achievement '{}' archived", achievement.name);
+    Ok(())
+}
+
+pub fn close_achievement(ctx: Context<CloseAchievement>) -> Result<()> {
+    let achievement = &ctx.accounts.achievement;
+    let brand = &mut ctx.accounts.brand;
+
+    brand.achievements.retain(|a| *a != achievement.key());
+
+    msg!(
+        "Achievement '{}' closed for Brand '{}'",
+        achievement.name,
+        brand.name
+    );
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct CreateAchievement<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
     pub brand: Account<'info, Brand>,
     #[account(
         init,
         payer = authority,
-        space = 8 + 32 + 50 + 200 + 200 + 4 + 8 + 8
+        space = 8 + 32 + 50 + 200 + 200 + 4 + 8 + 8 + 1 + 1 + 33
+            + 4 + ThresholdRule::SPACE * Achievement::MAX_RULES
+            + 4 + 32 * Achievement::MAX_PREREQUISITES
+            + 9 + 9 + 9 + 8
     )]
     pub achievement: Account<'info, Achievement>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
     #[account(mut)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -343,16 +1485,23 @@ pub struct CreateFungibleAchievement<'info> {
             + 1 // Achievement Type
             + 32 // Token Mint
             + 8 // Token Supply
+            + 1 // Archived
+            + 1 // Soulbound
+            + 33 // Merkle Tree
+            + 4 + ThresholdRule::SPACE * Achievement::MAX_RULES // Rules
+            + 4 + 32 * Achievement::MAX_PREREQUISITES // Prerequisites
+            + 1 + 8 // Available From
+            + 1 + 8 // Available Until
+            + 1 + 8 // Max Awards
+            + 8 // Awarded Count
     )]
     pub achievement: Account<'info, Achievement>,
-    #[account(
-        init,
-        payer = tronic_admin,
-        mint::decimals = 0,
-        mint::authority = tronic_admin.key(),
-        mint::freeze_authority = tronic_admin.key(),
-    )]
-    pub token_mint: Account<'info, Mint>,
+    /// Created in the instruction so it can be owned by either token program
+    #[account(mut)]
+    pub token_mint: Signer<'info>,
+    /// CHECK: PDA set as the mint and freeze authority of the badge
+    #[account(seeds = [MINT_AUTHORITY_SEED, brand.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
 
     #[account(
         seeds = [b"program-state"],
@@ -363,7 +1512,7 @@ pub struct CreateFungibleAchievement<'info> {
     #[account(mut)]
     pub tronic_admin: Signer<'info>,
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub rent: Sysvar<'info, Rent>,
 }
 
@@ -371,56 +1520,153 @@ pub struct CreateFungibleAchievement<'info> {
 pub struct AwardFungibleAchievement<'info> {
     #[account(mut)]
     pub brand: Account<'info, Brand>,
+    /// CHECK: Award record, created in the instruction
     #[account(
-        init,
-        payer = authority,
-        space = 8 + 32 + 32 + 32 + 8
+        mut,
+        seeds = [b"user-achievement", achievement.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_achievement: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = achievement.brand == brand.key(),
+        constraint = achievement.token_mint == Some(token_mint.key()) @ CepError::InvalidAchievementType
     )]
-    pub user_achievement: Account<'info, UserAchievement>,
-    #[account(mut, constraint = achievement.brand == brand.key())]
     pub achievement: Account<'info, Achievement>,
     /// CHECK: This account is used to store the public key of the user receiving the achievement
     pub user: UncheckedAccount<'info>,
-    #[account(mut)]
+    #[account(mut, has_one = user)]
     pub user_achievements: Account<'info, UserAchievements>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = UserPoints::SPACE,
+        seeds = [b"user-points", brand.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_points: Account<'info, UserPoints>,
+    /// CHECK: The brand's leaderboard, ranked by lifetime points, updated
+    /// once the brand has created it
+    #[account(mut, seeds = [b"leaderboard", brand.key().as_ref()], bump)]
+    pub leaderboard: UncheckedAccount<'info>,
+    /// Season the award's points also count towards
+    #[account(mut, has_one = brand)]
+    pub season: Option<Account<'info, Season>>,
+    /// CHECK: The user's points PDA for `season`, created on first credit
+    #[account(mut)]
+    pub user_season_points: Option<UncheckedAccount<'info>>,
     #[account(mut)]
     pub authority: Signer<'info>,
+    /// Required when `authority` awards as a delegated attester
+    #[account(mut)]
+    pub attester: Option<Account<'info, Attester>>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
     pub system_program: Program<'info, System>,
     #[account(mut)]
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, token_interface::Mint>,
+    /// CHECK: The brand's PDA that mints and freezes badges
+    #[account(seeds = [MINT_AUTHORITY_SEED, brand.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
     #[account(
         init_if_needed,
         payer = authority,
         associated_token::mint = token_mint,
         associated_token::authority = user,
+        associated_token::token_program = token_program,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+    pub user_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[derive(Accounts)]
+pub struct BatchAwardFungibleAchievement<'info> {
+    pub brand: Account<'info, Brand>,
+    #[account(
+        mut,
+        constraint = achievement.brand == brand.key(),
+        constraint = achievement.token_mint == Some(token_mint.key()) @ CepError::InvalidAchievementType
+    )]
+    pub achievement: Account<'info, Achievement>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// Required when `authority` awards as a delegated attester
+    #[account(mut)]
+    pub attester: Option<Account<'info, Attester>>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    #[account(mut)]
+    pub token_mint: InterfaceAccount<'info, token_interface::Mint>,
+    /// CHECK: The brand's PDA that mints and freezes badges
+    #[account(seeds = [MINT_AUTHORITY_SEED, brand.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    /// CHECK: The brand's leaderboard, ranked by lifetime points, updated
+    /// once the brand has created it
+    #[account(mut, seeds = [b"leaderboard", brand.key().as_ref()], bump)]
+    pub leaderboard: UncheckedAccount<'info>,
+    /// Season the awarded points also count towards
+    #[account(mut, has_one = brand)]
+    pub season: Option<Account<'info, Season>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct AwardNonFungibleAchievement<'info> {
     #[account(mut)]
     pub brand: Account<'info, Brand>,
+    /// CHECK: Award record, created in the instruction
     #[account(
-        init,
-        payer = authority,
-        space = 8 + 32 + 32 + 32 + 8
+        mut,
+        seeds = [b"user-achievement", achievement.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_achievement: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = achievement.brand == brand.key(),
+        constraint = achievement.token_mint == Some(token_mint.key()) @ CepError::InvalidAchievementType
     )]
-    pub user_achievement: Account<'info, UserAchievement>,
-    #[account(mut, constraint = achievement.brand == brand.key())]
     pub achievement: Account<'info, Achievement>,
-    /// CHECK: This account is used to store the public key of the user receiving the achievement
+    /// CHECK: This account is used to store the public key of the user receiving the achievement.
+    /// Signs for soulbound achievements to approve the brand's mint authority as delegate
     pub user: UncheckedAccount<'info>,
-    #[account(mut)]
+    #[account(mut, has_one = user)]
     pub user_achievements: Account<'info, UserAchievements>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = UserPoints::SPACE,
+        seeds = [b"user-points", brand.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_points: Account<'info, UserPoints>,
+    /// CHECK: The brand's leaderboard, ranked by lifetime points, updated
+    /// once the brand has created it
+    #[account(mut, seeds = [b"leaderboard", brand.key().as_ref()], bump)]
+    pub leaderboard: UncheckedAccount<'info>,
+    /// Season the award's points also count towards
+    #[account(mut, has_one = brand)]
+    pub season: Option<Account<'info, Season>>,
+    /// CHECK: The user's points PDA for `season`, created on first credit
+    #[account(mut)]
+    pub user_season_points: Option<UncheckedAccount<'info>>,
     #[account(mut)]
     pub authority: Signer<'info>,
+    /// Required when `authority` awards as a delegated attester
+    #[account(mut)]
+    pub attester: Option<Account<'info, Attester>>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
     pub system_program: Program<'info, System>,
     #[account(mut)]
     pub token_mint: Account<'info, Mint>,
+    /// CHECK: The brand's PDA that freezes soulbound NFTs as the holder's delegate
+    #[account(seeds = [MINT_AUTHORITY_SEED, brand.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
     #[account(
         init_if_needed,
         payer = authority,
@@ -457,6 +1703,226 @@ pub struct AwardNonFungibleAchievement<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[derive(Accounts)]
+pub struct CreateCompressedAchievement<'info> {
+    #[account(mut)]
+    pub brand: Account<'info, Brand>,
+
+    #[account(
+        init,
+        payer = tronic_admin,
+        space = 8 // Discriminator
+            + 32 // Brand
+            + 50 // Name
+            + 200 // Description
+            + 200 // Criteria
+            + 4 // Points
+            + 8 // Created At
+            + 8 // Updated At
+            + 1 // Achievement Type
+            + 32 // Token Mint
+            + 8 // Token Supply
+            + 200 // Metadata URI
+            + 1 // Archived
+            + 1 // Soulbound
+            + 33 // Merkle Tree
+            + 4 + ThresholdRule::SPACE * Achievement::MAX_RULES // Rules
+            + 4 + 32 * Achievement::MAX_PREREQUISITES // Prerequisites
+            + 1 + 8 // Available From
+            + 1 + 8 // Available Until
+            + 1 + 8 // Max Awards
+            + 8 // Awarded Count
+    )]
+    pub achievement: Account<'info, Achievement>,
+
+    /// CHECK: Allocated by the caller for the account compression program,
+    /// which initializes it through Bubblegum
+    #[account(mut, owner = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: Bubblegum's tree config PDA, created by Bubblegum
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = bubblegum_program.key(),
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+    /// CHECK: Program PDA that owns the tree and signs every mint
+    #[account(seeds = [b"tree-creator", achievement.key().as_ref()], bump)]
+    pub tree_creator: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"program-state"],
+        bump,
+        constraint = program_state.tronic_admin == tronic_admin.key() @ CepError::UnauthorizedTronicAdmin
+    )]
+    pub program_state: Account<'info, ProgramState>,
+
+    #[account(mut)]
+    pub tronic_admin: Signer<'info>,
+
+    /// CHECK: Checked by address
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    /// CHECK: Checked by address
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    /// CHECK: Checked by address
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateCompressedAchievement<'info> {
+    fn bubblegum(&self) -> Bubblegum<'_, 'info> {
+        Bubblegum {
+            bubblegum_program: self.bubblegum_program.as_ref(),
+            tree_config: self.tree_config.as_ref(),
+            merkle_tree: self.merkle_tree.as_ref(),
+            payer: self.tronic_admin.as_ref(),
+            tree_creator: self.tree_creator.as_ref(),
+            log_wrapper: self.log_wrapper.as_ref(),
+            compression_program: self.compression_program.as_ref(),
+            system_program: self.system_program.as_ref(),
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct AwardCompressedAchievement<'info> {
+    pub brand: Account<'info, Brand>,
+    /// CHECK: Award record, created in the instruction
+    #[account(
+        mut,
+        seeds = [b"user-achievement", achievement.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_achievement: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        has_one = brand,
+        constraint = achievement.merkle_tree == Some(merkle_tree.key()) @ CepError::InvalidAchievementType
+    )]
+    pub achievement: Account<'info, Achievement>,
+    /// CHECK: Receives the compressed NFT as leaf owner and delegate
+    pub user: UncheckedAccount<'info>,
+    #[account(mut, has_one = user)]
+    pub user_achievements: Account<'info, UserAchievements>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = UserPoints::SPACE,
+        seeds = [b"user-points", brand.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_points: Account<'info, UserPoints>,
+    /// CHECK: The brand's leaderboard, ranked by lifetime points, updated
+    /// once the brand has created it
+    #[account(mut, seeds = [b"leaderboard", brand.key().as_ref()], bump)]
+    pub leaderboard: UncheckedAccount<'info>,
+    /// Season the award's points also count towards
+    #[account(mut, has_one = brand)]
+    pub season: Option<Account<'info, Season>>,
+    /// CHECK: The user's points PDA for `season`, created on first credit
+    #[account(mut)]
+    pub user_season_points: Option<UncheckedAccount<'info>>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// Required when `authority` awards as a delegated attester
+    #[account(mut)]
+    pub attester: Option<Account<'info, Attester>>,
+
+    /// CHECK: Verified against the achievement and by the compression program
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: Bubblegum's tree config PDA
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = bubblegum_program.key(),
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+    /// CHECK: Program PDA that owns the tree
+    #[account(seeds = [b"tree-creator", achievement.key().as_ref()], bump)]
+    pub tree_creator: UncheckedAccount<'info>,
+
+    /// CHECK: Checked by address
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    /// CHECK: Checked by address
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    /// CHECK: Checked by address
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AwardCompressedAchievement<'info> {
+    fn bubblegum(&self) -> Bubblegum<'_, 'info> {
+        Bubblegum {
+            bubblegum_program: self.bubblegum_program.as_ref(),
+            tree_config: self.tree_config.as_ref(),
+            merkle_tree: self.merkle_tree.as_ref(),
+            payer: self.authority.as_ref(),
+            tree_creator: self.tree_creator.as_ref(),
+            log_wrapper: self.log_wrapper.as_ref(),
+            compression_program: self.compression_program.as_ref(),
+            system_program: self.system_program.as_ref(),
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct ThawAndBurn<'info> {
+    /// Brand whose mint authority froze the token
+    pub brand: Account<'info, Brand>,
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = holder,
+        token::token_program = token_program,
+    )]
+    pub token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    pub holder: Signer<'info>,
+    /// The mint's freeze authority, required for tokens it froze itself
+    pub freeze_authority: Option<Signer<'info>>,
+    /// CHECK: The brand's PDA that freezes badges and delegates soulbound NFTs
+    #[account(seeds = [MINT_AUTHORITY_SEED, brand.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    /// CHECK: Master edition of a soulbound NFT's mint, checked by Token Metadata
+    pub edition: Option<UncheckedAccount<'info>>,
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeSoulbound<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(mut, token::mint = mint, token::token_program = token_program)]
+    pub token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    pub authority: Signer<'info>,
+    /// CHECK: The brand's PDA that burns the token as its delegate
+    #[account(seeds = [MINT_AUTHORITY_SEED, brand.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    /// CHECK: Master edition of a soulbound NFT's mint, checked by Token Metadata
+    pub edition: Option<UncheckedAccount<'info>>,
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
 #[derive(Accounts)]
 pub struct InitializeUserAchievements<'info> {
     #[account(
@@ -471,6 +1937,36 @@ pub struct InitializeUserAchievements<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct UpdateAchievement<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(mut, has_one = brand)]
+    pub achievement: Account<'info, Achievement>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAchievement<'info> {
+    #[account(
+        mut,
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(mut, has_one = brand, close = recipient)]
+    pub achievement: Account<'info, Achievement>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    pub authority: Signer<'info>,
+    /// CHECK: Only receives the reclaimed rent
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
 #[derive(Accounts)]
 pub struct GetAchievementInfo<'info> {
     pub achievement: Account<'info, Achievement>,
@@ -485,6 +1981,11 @@ pub struct AchievementInfo {
     pub brand: Pubkey,
     pub created_at: i64,
     pub updated_at: i64,
+    pub archived: bool,
+    pub available_from: Option<i64>,
+    pub available_until: Option<i64>,
+    pub max_awards: Option<u64>,
+    pub awarded_count: u64,
 }
 
 #[derive(Accounts)]
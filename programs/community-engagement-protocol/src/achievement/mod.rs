@@ -2,12 +2,16 @@ pub mod instructions;
 pub mod state;
 
 pub use instructions::{
-    award_fungible_achievement, award_non_fungible_achievement, create_achievement,
-    create_fungible_achievement, create_non_fungible_achievement, get_achievement_info,
-    initialize_user_achievements, list_user_achievements, AchievementInfo,
-    AwardFungibleAchievement, AwardNonFungibleAchievement, CreateAchievement,
-    CreateFungibleAchievement, CreateNonFungibleAchievement, GetAchievementInfo,
-    InitializeUserAchievements, ListUserAchievements,
+    archive_achievement, award_compressed_achievement, award_fungible_achievement,
+    award_non_fungible_achievement, batch_award_fungible_achievement, close_achievement,
+    create_achievement, create_compressed_achievement, create_fungible_achievement,
+    create_non_fungible_achievement, get_achievement_info, initialize_user_achievements,
+    list_user_achievements, thaw_and_burn, update_achievement, AchievementInfo,
+    AwardCompressedAchievement, AwardFungibleAchievement, AwardNonFungibleAchievement,
+    BatchAwardFungibleAchievement, CloseAchievement, CreateAchievement,
+    CreateCompressedAchievement, CreateFungibleAchievement, CreateNonFungibleAchievement,
+    GetAchievementInfo, InitializeUserAchievements, ListUserAchievements, ThawAndBurn,
+    UpdateAchievement,
 };
 
 pub use state::{Achievement, AchievementType, UserAchievement, UserAchievements};
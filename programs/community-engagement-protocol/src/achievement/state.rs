@@ -1,9 +1,28 @@
+use crate::errors::CepError;
 use anchor_lang::prelude::*;
 
+/// Seed of the `[MINT_AUTHORITY_SEED, brand]` PDA that mints and freezes a
+/// brand's fungible achievement badges, so brand admins and attesters can
+/// award them without the Tronic admin key.
+pub const MINT_AUTHORITY_SEED: &[u8] = b"mint-authority";
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
 pub enum AchievementType {
     NonFungible,
     Fungible,
+    Compressed,
+}
+
+/// `metric >= threshold` on the user's progress counters.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct ThresholdRule {
+    pub metric: String,
+    pub threshold: u64,
+}
+
+impl ThresholdRule {
+    pub const MAX_METRIC_LENGTH: usize = 32;
+    pub const SPACE: usize = 4 + Self::MAX_METRIC_LENGTH + 8;
 }
 
 #[account]
@@ -19,6 +38,47 @@ pub struct Achievement {
     pub token_mint: Option<Pubkey>,
     pub token_supply: Option<u64>,
     pub metadata_uri: Option<String>,
+    pub archived: bool,
+    pub soulbound: bool,
+    pub merkle_tree: Option<Pubkey>,
+    pub rules: Vec<ThresholdRule>, // All must hold for the achievement to auto-award
+    pub prerequisites: Vec<Pubkey>, // Achievements the user must already hold
+    pub available_from: Option<i64>, // Achievement can't be awarded before this time
+    pub available_until: Option<i64>, // Achievement can't be awarded after this time
+    pub max_awards: Option<u64>,   // Cap on the number of awards
+    pub awarded_count: u64,        // Number of times this achievement has been awarded
+}
+
+impl Achievement {
+    /// Bytes reserved for `criteria` at creation
+    pub const MAX_CRITERIA_LENGTH: usize = 200;
+    pub const MAX_RULES: usize = 3;
+    pub const MAX_PREREQUISITES: usize = 5;
+
+    /// Checks that `count` more awards fit the availability window and cap,
+    /// then counts them.
+    pub fn record_awards(&mut self, now: i64, count: u64) -> Result<()> {
+        if let Some(available_from) = self.available_from {
+            require!(now >= available_from, CepError::AchievementNotYetAvailable);
+        }
+        if let Some(available_until) = self.available_until {
+            require!(now <= available_until, CepError::AchievementClosed);
+        }
+        let awarded_count = self.awarded_count.saturating_add(count);
+        if let Some(max_awards) = self.max_awards {
+            require!(awarded_count <= max_awards, CepError::AchievementSoldOut);
+        }
+        self.awarded_count = awarded_count;
+        Ok(())
+    }
+
+    /// Checks that `records` include one of the user's `UserAchievement`
+    /// accounts for every prerequisite, in any order.
+    pub fn has_prerequisites(&self, user: &Pubkey, records: &[AccountInfo]) -> bool {
+        self.prerequisites
+            .iter()
+            .all(|prerequisite| UserAchievement::find(records, user, prerequisite).is_some())
+    }
 }
 
 #[account]
@@ -29,6 +89,20 @@ pub struct UserAchievement {
     pub awarded_at: i64,
 }
 
+impl UserAchievement {
+    /// Looks through `records` for the award of `achievement` to `user`.
+    pub fn find(records: &[AccountInfo], user: &Pubkey, achievement: &Pubkey) -> Option<Self> {
+        records
+            .iter()
+            .filter(|record| record.owner == &crate::ID)
+            .filter_map(|record| {
+                let data = record.try_borrow_data().ok()?;
+                Self::try_deserialize(&mut &data[..]).ok()
+            })
+            .find(|held| held.user == *user && held.achievement == *achievement)
+    }
+}
+
 #[account]
 pub struct UserAchievements {
     pub user: Pubkey,
@@ -0,0 +1,126 @@
+use super::state::*;
+use crate::brand::state::Brand;
+use crate::errors::CepError;
+use crate::ProgramState;
+use anchor_lang::prelude::*;
+
+pub fn create_attester(
+    ctx: Context<CreateAttester>,
+    attester_key: Pubkey,
+    achievements: Vec<Pubkey>,
+    all_achievements: bool,
+    daily_quota: u32,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    require!(
+        achievements.len() <= Attester::MAX_ACHIEVEMENTS,
+        CepError::TooManyAttesterAchievements
+    );
+
+    let attester = &mut ctx.accounts.attester;
+    attester.brand = ctx.accounts.brand.key();
+    attester.attester = attester_key;
+    attester.achievements = achievements;
+    attester.all_achievements = all_achievements;
+    attester.daily_quota = daily_quota;
+    attester.expires_at = expires_at;
+    attester.current_day = 0;
+    attester.awarded_today = 0;
+
+    msg!(
+        "Attester {} added to Brand '{}'",
+        attester_key,
+        ctx.accounts.brand.name
+    );
+    Ok(())
+}
+
+pub fn update_attester(
+    ctx: Context<UpdateAttester>,
+    achievements: Vec<Pubkey>,
+    all_achievements: bool,
+    daily_quota: u32,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    require!(
+        achievements.len() <= Attester::MAX_ACHIEVEMENTS,
+        CepError::TooManyAttesterAchievements
+    );
+
+    let attester = &mut ctx.accounts.attester;
+    attester.achievements = achievements;
+    attester.all_achievements = all_achievements;
+    attester.daily_quota = daily_quota;
+    attester.expires_at = expires_at;
+
+    msg!("Attester {} updated", attester.attester);
+    Ok(())
+}
+
+pub fn revoke_attester(ctx: Context<RevokeAttester>) -> Result<()> {
+    msg!(
+        "Attester {} revoked for Brand '{}'",
+        ctx.accounts.attester.attester,
+        ctx.accounts.brand.name
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(attester_key: Pubkey)]
+pub struct CreateAttester<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 // Discriminator
+            + 32 // Brand
+            + 32 // Attester
+            + 4 + 32 * Attester::MAX_ACHIEVEMENTS // Achievements
+            + 1 // All achievements
+            + 4 // Daily quota
+            + 1 + 8 // Expires at
+            + 8 // Current day
+            + 4, // Awarded today
+        seeds = [b"attester", brand.key().as_ref(), attester_key.as_ref()],
+        bump
+    )]
+    pub attester: Account<'info, Attester>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAttester<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(mut, has_one = brand)]
+    pub attester: Account<'info, Attester>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeAttester<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(mut, has_one = brand, close = recipient)]
+    pub attester: Account<'info, Attester>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    pub authority: Signer<'info>,
+    /// CHECK: Only receives the reclaimed rent
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
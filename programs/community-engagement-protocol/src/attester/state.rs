@@ -0,0 +1,89 @@
+use crate::brand::state::Brand;
+use crate::errors::CepError;
+use anchor_lang::prelude::*;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+#[account]
+pub struct Attester {
+    pub brand: Pubkey,
+    pub attester: Pubkey,
+    pub achievements: Vec<Pubkey>, // Achievements this attester may award
+    pub all_achievements: bool,    // Wildcard over every achievement of the brand
+    pub daily_quota: u32,          // Awards allowed per UTC day
+    pub expires_at: Option<i64>,   // Attester can't award after this time
+    pub current_day: i64,          // Day `awarded_today` counts for
+    pub awarded_today: u32,
+}
+
+impl Attester {
+    pub const MAX_ACHIEVEMENTS: usize = 10;
+
+    /// Checks the grant covers `achievement` and takes `count` awards out of
+    /// today's quota.
+    pub fn consume(&mut self, achievement: &Pubkey, now: i64, count: u32) -> Result<()> {
+        if let Some(expires_at) = self.expires_at {
+            require!(now < expires_at, CepError::AttesterExpired);
+        }
+        require!(
+            self.all_achievements || self.achievements.contains(achievement),
+            CepError::AttesterNotAllowed
+        );
+
+        let today = now.div_euclid(SECONDS_PER_DAY);
+        if today != self.current_day {
+            self.current_day = today;
+            self.awarded_today = 0;
+        }
+        self.awarded_today = self
+            .awarded_today
+            .checked_add(count)
+            .filter(|awarded| *awarded <= self.daily_quota)
+            .ok_or(CepError::AttesterQuotaExceeded)?;
+        Ok(())
+    }
+}
+
+/// Checks that `authority` may award `count` of `achievement`: the Tronic
+/// admin and the brand owner always can, an attester only within its grant.
+pub fn authorize_award(
+    brand: &Account<Brand>,
+    achievement: &Pubkey,
+    authority: &Pubkey,
+    tronic_admin: &Pubkey,
+    attester: Option<&mut Account<Attester>>,
+    count: u32,
+) -> Result<()> {
+    if brand.is_authority(authority, tronic_admin) {
+        return Ok(());
+    }
+
+    let attester = attester.ok_or(CepError::Unauthorized)?;
+    require_keys_eq!(attester.brand, brand.key(), CepError::Unauthorized);
+    require_keys_eq!(attester.attester, *authority, CepError::Unauthorized);
+    attester.consume(achievement, Clock::get()?.unix_timestamp, count)
+}
+
+/// Checks that `authority` may record engagement for the brand without
+/// awarding anything: brand authorities always can, attesters until expiry.
+pub fn authorize_activity(
+    brand: &Account<Brand>,
+    authority: &Pubkey,
+    tronic_admin: &Pubkey,
+    attester: Option<&Account<Attester>>,
+) -> Result<()> {
+    if brand.is_authority(authority, tronic_admin) {
+        return Ok(());
+    }
+
+    let attester = attester.ok_or(CepError::Unauthorized)?;
+    require_keys_eq!(attester.brand, brand.key(), CepError::Unauthorized);
+    require_keys_eq!(attester.attester, *authority, CepError::Unauthorized);
+    if let Some(expires_at) = attester.expires_at {
+        require!(
+            Clock::get()?.unix_timestamp < expires_at,
+            CepError::AttesterExpired
+        );
+    }
+    Ok(())
+}
@@ -1,9 +1,12 @@
 use super::state::*;
+use crate::utils::{load_or_create_account, store_account};
 use crate::{errors::CepError, ProgramState};
 use anchor_lang::prelude::*;
 
+#[allow(clippy::too_many_arguments)]
 pub fn create_brand(
     ctx: Context<CreateBrand>,
+    brand_id: u64,
     name: String,
     description: String,
     website: Option<String>,
@@ -27,6 +30,25 @@ pub fn create_brand(
         return Err(CepError::TooManyTags.into());
     }
 
+    // The registry of a free name is created empty
+    let name_registry_info = ctx.accounts.name_registry.to_account_info();
+    let mut name_registry: BrandNameRegistry = load_or_create_account(
+        &name_registry_info,
+        &[b"brand-name", &BrandNameRegistry::seed(&name)],
+        BrandNameRegistry::SPACE,
+        &ctx.accounts.tronic_admin,
+        &ctx.accounts.system_program,
+    )?;
+    require!(
+        name_registry.brand == Pubkey::default(),
+        CepError::BrandNameTaken
+    );
+    name_registry.brand = brand.key();
+    store_account(&name_registry_info, &name_registry)?;
+
+    brand.brand_id = brand_id;
+    brand.owner = ctx.accounts.tronic_admin.key();
+    brand.pending_owner = None;
     brand.name = name;
     brand.description = description;
     brand.achievements = Vec::new();
@@ -58,6 +80,37 @@ pub fn update_brand(ctx: Context<UpdateBrand>, name: String, description: String
         return Err(CepError::DescriptionTooLong.into());
     }
 
+    // Release the old name and claim the new one in the same instruction, so
+    // the brand stays resolvable by name and the old name becomes available again
+    let current_name_registry = &ctx.accounts.current_name_registry;
+    require_keys_eq!(
+        current_name_registry.key(),
+        BrandNameRegistry::address(&brand.name),
+        ErrorCode::ConstraintSeeds
+    );
+    let new_name_registry_info = ctx.accounts.new_name_registry.to_account_info();
+    require_keys_eq!(
+        new_name_registry_info.key(),
+        BrandNameRegistry::address(&name),
+        ErrorCode::ConstraintSeeds
+    );
+    if new_name_registry_info.key() != current_name_registry.key() {
+        let mut new_name_registry: BrandNameRegistry = load_or_create_account(
+            &new_name_registry_info,
+            &[b"brand-name", &BrandNameRegistry::seed(&name)],
+            BrandNameRegistry::SPACE,
+            &ctx.accounts.tronic_admin,
+            &ctx.accounts.system_program,
+        )?;
+        require!(
+            new_name_registry.brand == Pubkey::default(),
+            CepError::BrandNameTaken
+        );
+        new_name_registry.brand = brand.key();
+        store_account(&new_name_registry_info, &new_name_registry)?;
+        current_name_registry.close(ctx.accounts.tronic_admin.to_account_info())?;
+    }
+
     brand.name = name;
     brand.description = description;
     brand.last_updated = clock.unix_timestamp;
@@ -66,9 +119,56 @@ pub fn update_brand(ctx: Context<UpdateBrand>, name: String, description: String
     Ok(())
 }
 
+pub fn transfer_brand_ownership(
+    ctx: Context<TransferBrandOwnership>,
+    new_owner: Pubkey,
+) -> Result<()> {
+    let brand = &mut ctx.accounts.brand;
+    brand.pending_owner = Some(new_owner);
+
+    msg!(
+        "Brand '{}' ownership transfer proposed to {}",
+        brand.name,
+        new_owner
+    );
+    Ok(())
+}
+
+pub fn accept_brand_ownership(ctx: Context<AcceptBrandOwnership>) -> Result<()> {
+    let brand = &mut ctx.accounts.brand;
+    let clock = Clock::get()?;
+
+    brand.owner = ctx.accounts.new_owner.key();
+    brand.pending_owner = None;
+    brand.last_updated = clock.unix_timestamp;
+
+    msg!("Brand '{}' is now owned by {}", brand.name, brand.owner);
+    Ok(())
+}
+
+pub fn close_brand(ctx: Context<CloseBrand>) -> Result<()> {
+    let brand = &ctx.accounts.brand;
+
+    require!(
+        brand.achievements.is_empty() && brand.memberships.is_empty(),
+        CepError::BrandNotEmpty
+    );
+    require_keys_eq!(
+        ctx.accounts.name_registry.key(),
+        BrandNameRegistry::address(&brand.name),
+        ErrorCode::ConstraintSeeds
+    );
+
+    ctx.accounts.brand_list.remove(&brand.key());
+
+    msg!("Brand '{}' closed", brand.name);
+    Ok(())
+}
+
 pub fn get_brand_info(ctx: Context<GetBrandInfo>) -> Result<BrandInfo> {
     let brand = &ctx.accounts.brand;
     Ok(BrandInfo {
+        owner: brand.owner,
         name: brand.name.clone(),
         description: brand.description.clone(),
         achievements: brand.achievements.clone(),
@@ -85,12 +185,15 @@ pub fn list_brand_achievements(ctx: Context<ListBrandAchievements>) -> Result<Ve
 }
 
 #[derive(Accounts)]
-#[instruction(name: String)]
+#[instruction(brand_id: u64)]
 pub struct CreateBrand<'info> {
     #[account(
         init,
         payer = tronic_admin,
         space = 8 + // discriminator
+                8 + // brand_id (u64)
+                32 + // owner (Pubkey)
+                (1 + 32) + // pending_owner (Option<Pubkey>)
                 50 + // name (String)
                 200 + // description (String)
                 32 + (4 + 32 * 50) + // achievements (Vec<Pubkey>)
@@ -101,10 +204,14 @@ pub struct CreateBrand<'info> {
                 (1 + 50) + // social_media (Option<String>)
                 (1 + 20) + // category (Option<String>)
                 (4 + 5 * 20), // tags (Vec<String>)
-        seeds = [b"brand", name.as_bytes()],
+        seeds = [b"brand", brand_id.to_le_bytes().as_ref()],
         bump
     )]
     pub brand: Account<'info, Brand>,
+    /// CHECK: The registry of the brand's name, created on first claim and
+    /// validated in the handler
+    #[account(mut)]
+    pub name_registry: UncheckedAccount<'info>,
     #[account(
         init_if_needed,
         payer = tronic_admin,
@@ -128,13 +235,60 @@ pub struct CreateBrand<'info> {
 pub struct UpdateBrand<'info> {
     #[account(mut)]
     pub brand: Account<'info, Brand>,
+    /// The registry of the brand's current name, checked against it in the handler
+    #[account(mut, has_one = brand)]
+    pub current_name_registry: Account<'info, BrandNameRegistry>,
+    /// CHECK: The registry of the new name, created on first claim and
+    /// validated in the handler
+    #[account(mut)]
+    pub new_name_registry: UncheckedAccount<'info>,
     #[account(
         seeds = [b"program-state"],
         bump,
         constraint = program_state.tronic_admin == tronic_admin.key() @ CepError::UnauthorizedTronicAdmin
     )]
     pub program_state: Account<'info, ProgramState>,
+    #[account(mut)]
     pub tronic_admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TransferBrandOwnership<'info> {
+    #[account(mut, has_one = owner @ CepError::Unauthorized)]
+    pub brand: Account<'info, Brand>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptBrandOwnership<'info> {
+    #[account(
+        mut,
+        constraint = brand.pending_owner == Some(new_owner.key()) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    pub new_owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseBrand<'info> {
+    #[account(
+        mut,
+        close = recipient,
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    /// The registry of the brand's name, checked against it in the handler
+    #[account(mut, close = recipient, has_one = brand)]
+    pub name_registry: Account<'info, BrandNameRegistry>,
+    #[account(mut, seeds = [b"brand-list"], bump)]
+    pub brand_list: Account<'info, BrandList>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    pub authority: Signer<'info>,
+    /// CHECK: Only receives the reclaimed rent
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
 }
 
 #[derive(Accounts)]
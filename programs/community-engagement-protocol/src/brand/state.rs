@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
 
 #[account]
 pub struct Brand {
+    pub brand_id: u64,
+    pub owner: Pubkey,
+    pub pending_owner: Option<Pubkey>,
     pub name: String,
     pub description: String,
     pub achievements: Vec<Pubkey>,
@@ -11,6 +15,33 @@ pub struct Brand {
     pub metadata: BrandMetadata,
 }
 
+/// Claims a brand name so it can be resolved to the brand that currently holds it.
+/// Seeded by the hash of the name, since names can be longer than a single PDA seed.
+#[account]
+#[derive(Default)]
+pub struct BrandNameRegistry {
+    pub brand: Pubkey,
+}
+
+impl BrandNameRegistry {
+    pub const SPACE: usize = 8 + 32; // discriminator + brand pubkey
+
+    pub fn seed(name: &str) -> [u8; 32] {
+        hash(name.as_bytes()).to_bytes()
+    }
+
+    pub fn address(name: &str) -> Pubkey {
+        Pubkey::find_program_address(&[b"brand-name", &Self::seed(name)], &crate::ID).0
+    }
+}
+
+impl Brand {
+    /// Brand-level management is open to the brand owner and the Tronic admin.
+    pub fn is_authority(&self, key: &Pubkey, tronic_admin: &Pubkey) -> bool {
+        self.owner == *key || tronic_admin == key
+    }
+}
+
 #[account]
 pub struct BrandList {
     pub brands: Vec<Pubkey>,
@@ -21,6 +52,10 @@ impl BrandList {
         self.brands.push(brand);
     }
 
+    pub fn remove(&mut self, brand: &Pubkey) {
+        self.brands.retain(|b| b != brand);
+    }
+
     pub fn get_all(&self) -> Vec<Pubkey> {
         self.brands.clone()
     }
@@ -28,6 +63,7 @@ impl BrandList {
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
 pub struct BrandInfo {
+    pub owner: Pubkey,
     pub name: String,
     pub description: String,
     pub achievements: Vec<Pubkey>,
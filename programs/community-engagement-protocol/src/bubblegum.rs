@@ -0,0 +1,201 @@
+// File: src/bubblegum.rs
+
+use anchor_lang::prelude::*;
+use anchor_lang::pubkey;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+
+pub const BUBBLEGUM_PROGRAM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+pub const ACCOUNT_COMPRESSION_PROGRAM_ID: Pubkey =
+    pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+pub const NOOP_PROGRAM_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+/// Bubblegum limits compressed NFT names to 32 bytes.
+pub const MAX_NAME_LENGTH: usize = 32;
+
+const CREATE_TREE_CONFIG_DISCRIMINATOR: [u8; 8] = [165, 83, 136, 142, 89, 202, 47, 220];
+const MINT_V1_DISCRIMINATOR: [u8; 8] = [145, 98, 192, 118, 184, 147, 118, 104];
+
+// Mirrors of the Bubblegum argument types; only their Borsh layout matters
+#[derive(AnchorSerialize)]
+pub enum TokenStandard {
+    NonFungible,
+    FungibleAsset,
+    Fungible,
+    NonFungibleEdition,
+}
+
+#[derive(AnchorSerialize)]
+pub enum TokenProgramVersion {
+    Original,
+    Token2022,
+}
+
+#[derive(AnchorSerialize)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+#[derive(AnchorSerialize)]
+pub enum UseMethod {
+    Burn,
+    Multiple,
+    Single,
+}
+
+#[derive(AnchorSerialize)]
+pub struct Uses {
+    pub use_method: UseMethod,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+#[derive(AnchorSerialize)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+#[derive(AnchorSerialize)]
+pub struct MetadataArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<TokenStandard>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+    pub token_program_version: TokenProgramVersion,
+    pub creators: Vec<Creator>,
+}
+
+impl MetadataArgs {
+    /// Plain, mutable, royalty-free cNFT metadata.
+    pub fn new(name: String, uri: String) -> Self {
+        Self {
+            name,
+            symbol: String::new(),
+            uri,
+            seller_fee_basis_points: 0,
+            primary_sale_happened: false,
+            is_mutable: true,
+            edition_nonce: None,
+            token_standard: Some(TokenStandard::NonFungible),
+            collection: None,
+            uses: None,
+            token_program_version: TokenProgramVersion::Original,
+            creators: Vec::new(),
+        }
+    }
+}
+
+/// Accounts shared by the Bubblegum instructions used here. `tree_creator`
+/// is a program PDA and signs with `signer_seeds`.
+pub struct Bubblegum<'a, 'info> {
+    pub bubblegum_program: &'a AccountInfo<'info>,
+    pub tree_config: &'a AccountInfo<'info>,
+    pub merkle_tree: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub tree_creator: &'a AccountInfo<'info>,
+    pub log_wrapper: &'a AccountInfo<'info>,
+    pub compression_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> Bubblegum<'a, 'info> {
+    /// Initializes the tree config for a Merkle tree already allocated and
+    /// owned by the account compression program.
+    pub fn create_tree_config(
+        &self,
+        max_depth: u32,
+        max_buffer_size: u32,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let mut data = CREATE_TREE_CONFIG_DISCRIMINATOR.to_vec();
+        // Args: max_depth, max_buffer_size, public (None keeps the tree private)
+        (max_depth, max_buffer_size, None::<bool>).serialize(&mut data)?;
+
+        let instruction = Instruction {
+            program_id: BUBBLEGUM_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(self.tree_config.key(), false),
+                AccountMeta::new(self.merkle_tree.key(), false),
+                AccountMeta::new(self.payer.key(), true),
+                AccountMeta::new_readonly(self.tree_creator.key(), true),
+                AccountMeta::new_readonly(self.log_wrapper.key(), false),
+                AccountMeta::new_readonly(self.compression_program.key(), false),
+                AccountMeta::new_readonly(self.system_program.key(), false),
+            ],
+            data,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[
+                self.tree_config.clone(),
+                self.merkle_tree.clone(),
+                self.payer.clone(),
+                self.tree_creator.clone(),
+                self.log_wrapper.clone(),
+                self.compression_program.clone(),
+                self.system_program.clone(),
+                self.bubblegum_program.clone(),
+            ],
+            signer_seeds,
+        )?;
+
+        Ok(())
+    }
+
+    /// Appends a compressed NFT owned and delegated to `leaf_owner`.
+    pub fn mint_v1(
+        &self,
+        leaf_owner: &AccountInfo<'info>,
+        metadata: MetadataArgs,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let mut data = MINT_V1_DISCRIMINATOR.to_vec();
+        metadata.serialize(&mut data)?;
+
+        let instruction = Instruction {
+            program_id: BUBBLEGUM_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(self.tree_config.key(), false),
+                AccountMeta::new_readonly(leaf_owner.key(), false),
+                AccountMeta::new_readonly(leaf_owner.key(), false),
+                AccountMeta::new(self.merkle_tree.key(), false),
+                AccountMeta::new(self.payer.key(), true),
+                AccountMeta::new_readonly(self.tree_creator.key(), true),
+                AccountMeta::new_readonly(self.log_wrapper.key(), false),
+                AccountMeta::new_readonly(self.compression_program.key(), false),
+                AccountMeta::new_readonly(self.system_program.key(), false),
+            ],
+            data,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[
+                self.tree_config.clone(),
+                leaf_owner.clone(),
+                self.merkle_tree.clone(),
+                self.payer.clone(),
+                self.tree_creator.clone(),
+                self.log_wrapper.clone(),
+                self.compression_program.clone(),
+                self.system_program.clone(),
+                self.bubblegum_program.clone(),
+            ],
+            signer_seeds,
+        )?;
+
+        Ok(())
+    }
+}
@@ -35,4 +35,142 @@ pub enum CepError {
     AlreadyInitialized,
     #[msg("Invalid initial admin")]
     InvalidInitialAdmin,
+    #[msg("This brand name is already taken")]
+    BrandNameTaken,
+    #[msg("Brand still has live memberships or achievements")]
+    BrandNotEmpty,
+    #[msg("Achievement has been archived")]
+    AchievementArchived,
+    #[msg("Reward is not active")]
+    RewardInactive,
+    #[msg("Reward is not available yet")]
+    RewardNotStarted,
+    #[msg("Reward is no longer available")]
+    RewardEnded,
+    #[msg("Schedule start must be before its end")]
+    InvalidSchedule,
+    #[msg("Token metadata and non-transferable mints require the Token-2022 program")]
+    Token2022Required,
+    #[msg("Achievement is not of the expected type")]
+    InvalidAchievementType,
+    #[msg("Batch accounts must come in complete per-user groups")]
+    InvalidBatchAccounts,
+    #[msg("Too many users in one batch")]
+    BatchTooLarge,
+    #[msg("Attester has expired")]
+    AttesterExpired,
+    #[msg("Attester is not allowed to award this achievement")]
+    AttesterNotAllowed,
+    #[msg("Attester daily quota exceeded")]
+    AttesterQuotaExceeded,
+    #[msg("A maximum of 10 achievements can be granted to an attester")]
+    TooManyAttesterAchievements,
+    #[msg("A maximum of 3 rules can be set on an achievement")]
+    TooManyRules,
+    #[msg("Metric names must be unique and 1 to 32 bytes long")]
+    InvalidMetric,
+    #[msg("Metric is not used by any of the achievement's rules")]
+    UnknownMetric,
+    #[msg("User does not hold every prerequisite achievement")]
+    PrerequisiteMissing,
+    #[msg("A maximum of 5 prerequisites can be set on an achievement")]
+    TooManyPrerequisites,
+    #[msg("An achievement cannot be its own prerequisite")]
+    InvalidPrerequisite,
+    #[msg("A quest needs between 1 and 10 achievements")]
+    InvalidQuestSteps,
+    #[msg("Quest deadline has passed")]
+    QuestExpired,
+    #[msg("User has not earned every achievement of the quest")]
+    QuestIncomplete,
+    #[msg("Quest achievements were not earned in order")]
+    QuestStepsOutOfOrder,
+    #[msg("Reward, token mint and user token account are required to issue the reward")]
+    RewardAccountsRequired,
+    #[msg("Streak period must be positive and its grace window shorter than the period")]
+    InvalidStreakPeriod,
+    #[msg("A streak can have up to 5 milestones of distinct, non-zero lengths")]
+    InvalidStreakMilestones,
+    #[msg("The reached milestone's achievement accounts are required")]
+    MilestoneAccountsRequired,
+    #[msg("Achievement cannot be awarded yet")]
+    AchievementNotYetAvailable,
+    #[msg("Achievement can no longer be awarded")]
+    AchievementClosed,
+    #[msg("Achievement has reached its maximum number of awards")]
+    AchievementSoldOut,
+    #[msg("Season is not running")]
+    SeasonNotActive,
+    #[msg("Season and user season points accounts must be passed together")]
+    SeasonAccountsRequired,
+    #[msg("Season has not ended yet")]
+    SeasonNotEnded,
+    #[msg("Season has already been closed")]
+    SeasonClosed,
+    #[msg("Program account does not match the expected address")]
+    InvalidProgramAccount,
+    #[msg("Points policy needs a period of at least one day")]
+    InvalidPointsPolicy,
+    #[msg("Not enough points available")]
+    InsufficientPoints,
+    #[msg("Points exchange needs two different brands and a non-zero rate")]
+    InvalidPointsExchange,
+    #[msg("Points exchange daily cap exceeded")]
+    ExchangeCapExceeded,
+    #[msg("Amount is too small to exchange for any points")]
+    ExchangeAmountTooSmall,
+    #[msg("Referral code must be between 1 and 32 bytes")]
+    InvalidReferralCode,
+    #[msg("Referral code and referral accounts must be passed together for the same brand")]
+    InvalidReferral,
+    #[msg("Members can't refer themselves")]
+    SelfReferral,
+    #[msg("Referral has already been credited")]
+    ReferralAlreadyCredited,
+    #[msg("Referrer has reached the maximum number of credited referrals")]
+    ReferralCapReached,
+    #[msg("Raffle needs 1 to 200 entries, 1 to 10 winners and a future close time")]
+    InvalidRaffle,
+    #[msg("Raffle is closed for entries")]
+    RaffleClosed,
+    #[msg("Raffle has reached its maximum number of entries")]
+    RaffleFull,
+    #[msg("User has already entered this raffle")]
+    AlreadyEnteredRaffle,
+    #[msg("User does not meet the raffle's entry requirement")]
+    RaffleRequirementNotMet,
+    #[msg("Raffle can't be drawn before it closes")]
+    RaffleNotClosed,
+    #[msg("Raffle has already been drawn")]
+    RaffleAlreadyDrawn,
+    #[msg("Randomness account is missing or doesn't answer this raffle")]
+    InvalidRandomness,
+    #[msg("User is not a winner of this raffle")]
+    NotRaffleWinner,
+    #[msg("Raffle prize has already been issued to this winner")]
+    RafflePrizeIssued,
+    #[msg("Criteria must be 200 bytes or less")]
+    CriteriaTooLong,
+    #[msg("Achievement has already been awarded to this user")]
+    AchievementAlreadyAwarded,
+    #[msg("Master edition and token metadata program are required to thaw a soulbound NFT")]
+    EditionAccountsRequired,
+    #[msg("The holder must sign to receive a soulbound NFT")]
+    SoulboundHolderSignatureRequired,
+    #[msg("The brand holds no delegate that can burn this token")]
+    SoulboundNotRevocable,
+    #[msg("Points balance is out of sync with its buckets")]
+    PointsBalanceMismatch,
+    #[msg("Raffle draw hasn't been scheduled or its draw slot hasn't been reached")]
+    RaffleDrawNotReady,
+    #[msg("Raffle draw has already been scheduled")]
+    RaffleDrawAlreadyScheduled,
+    #[msg("Hash of the raffle's draw slot is no longer available")]
+    DrawSlotHashExpired,
+    #[msg("Frozen soulbound tokens can only be burned in full")]
+    SoulboundPartialBurn,
+    #[msg("Reward does not match the referral program's reward")]
+    ReferralRewardMismatch,
+    #[msg("Token-2022 reward names and URIs live on the mint and can't be updated")]
+    RewardMetadataOnMint,
 }
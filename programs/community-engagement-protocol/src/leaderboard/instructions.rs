@@ -0,0 +1,63 @@
+use super::state::*;
+use crate::brand::state::Brand;
+use crate::errors::CepError;
+use crate::points::state::Standing;
+use crate::ProgramState;
+use anchor_lang::prelude::*;
+
+pub fn create_leaderboard(ctx: Context<CreateLeaderboard>) -> Result<()> {
+    let leaderboard = &mut ctx.accounts.leaderboard;
+    leaderboard.brand = ctx.accounts.brand.key();
+    leaderboard.standings = vec![];
+    leaderboard.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Leaderboard created for Brand '{}'",
+        ctx.accounts.brand.name
+    );
+    Ok(())
+}
+
+/// Returns up to `limit` standings starting at rank `offset`, capped at
+/// `Leaderboard::PAGE_SIZE` per call.
+pub fn get_leaderboard(
+    ctx: Context<GetLeaderboard>,
+    offset: u16,
+    limit: u8,
+) -> Result<Vec<Standing>> {
+    Ok(ctx
+        .accounts
+        .leaderboard
+        .standings
+        .iter()
+        .skip(offset.into())
+        .take(usize::from(limit).min(Leaderboard::PAGE_SIZE))
+        .cloned()
+        .collect())
+}
+
+#[derive(Accounts)]
+pub struct CreateLeaderboard<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(
+        init,
+        payer = authority,
+        space = Leaderboard::SPACE,
+        seeds = [b"leaderboard", brand.key().as_ref()],
+        bump
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetLeaderboard<'info> {
+    pub leaderboard: Account<'info, Leaderboard>,
+}
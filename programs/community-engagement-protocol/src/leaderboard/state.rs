@@ -0,0 +1,45 @@
+use crate::points::state::Standing;
+use crate::utils::store_account;
+use anchor_lang::prelude::*;
+
+/// Top users of a brand by lifetime points.
+#[account]
+pub struct Leaderboard {
+    pub brand: Pubkey,
+    pub standings: Vec<Standing>, // Best first, at most `CAPACITY` entries
+    pub updated_at: i64,
+}
+
+impl Leaderboard {
+    pub const CAPACITY: usize = 100;
+    /// Standings returned per `get_leaderboard` call, within the 1024 byte
+    /// return data limit.
+    pub const PAGE_SIZE: usize = 25;
+
+    pub const SPACE: usize = 8 // Discriminator
+        + 32 // Brand
+        + 4 + Standing::SPACE * Self::CAPACITY // Standings
+        + 8; // Updated at
+
+    pub fn record(&mut self, user: Pubkey, lifetime_earned: u64, now: i64) {
+        Standing::update(&mut self.standings, user, lifetime_earned, Self::CAPACITY);
+        self.updated_at = now;
+    }
+
+    /// Records `lifetime_earned` on the brand's `[b"leaderboard", brand]`
+    /// PDA at `info` once the brand has created its leaderboard.
+    pub fn record_if_created(
+        info: &AccountInfo,
+        user: Pubkey,
+        lifetime_earned: u64,
+        now: i64,
+    ) -> Result<()> {
+        if info.data_is_empty() {
+            return Ok(());
+        }
+        require_keys_eq!(*info.owner, crate::ID, ErrorCode::ConstraintOwner);
+        let mut leaderboard = Self::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        leaderboard.record(user, lifetime_earned, now);
+        store_account(info, &leaderboard)
+    }
+}
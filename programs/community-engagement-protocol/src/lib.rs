@@ -1,16 +1,42 @@
 use anchor_lang::prelude::*;
 
 pub mod achievement;
+pub mod attester;
 pub mod brand;
+pub mod bubblegum;
 pub mod errors;
+pub mod leaderboard;
 pub mod membership;
+pub mod points;
+pub mod progress;
+pub mod quest;
+pub mod raffle;
+pub mod referral;
 pub mod reward;
+pub mod season;
+pub mod streak;
+pub mod token_extensions;
+pub mod utils;
 
 use achievement::instructions::*;
+use achievement::state::ThresholdRule;
+use attester::instructions::*;
 use brand::instructions::*;
 use brand::state::BrandInfo;
+use leaderboard::instructions::*;
 use membership::instructions::*;
+use points::instructions::*;
+use points::state::{PointExpiry, Standing};
+use progress::instructions::*;
+use quest::instructions::*;
+use raffle::instructions::*;
+use raffle::state::EntryRequirement;
+use referral::instructions::*;
 use reward::instructions::*;
+use season::instructions::*;
+use streak::instructions::*;
+use streak::state::StreakMilestone;
+use token_extensions::TokenMetadataArgs;
 
 declare_id!("7FQ74JMt2Eeca2RD2aLVBv4No8e9PUt8SHfGsUzKhqje");
 
@@ -36,8 +62,10 @@ pub mod community_engagement_protocol {
     }
 
     // Brand Instructions
+    #[allow(clippy::too_many_arguments)]
     pub fn create_brand(
         ctx: Context<CreateBrand>,
+        brand_id: u64,
         name: String,
         description: String,
         website: Option<String>,
@@ -47,6 +75,7 @@ pub mod community_engagement_protocol {
     ) -> Result<()> {
         brand::instructions::create_brand(
             ctx,
+            brand_id,
             name,
             description,
             website,
@@ -64,6 +93,59 @@ pub mod community_engagement_protocol {
         brand::instructions::update_brand(ctx, name, description)
     }
 
+    pub fn transfer_brand_ownership(
+        ctx: Context<TransferBrandOwnership>,
+        new_owner: Pubkey,
+    ) -> Result<()> {
+        brand::instructions::transfer_brand_ownership(ctx, new_owner)
+    }
+
+    pub fn accept_brand_ownership(ctx: Context<AcceptBrandOwnership>) -> Result<()> {
+        brand::instructions::accept_brand_ownership(ctx)
+    }
+
+    pub fn close_brand(ctx: Context<CloseBrand>) -> Result<()> {
+        brand::instructions::close_brand(ctx)
+    }
+
+    pub fn create_attester(
+        ctx: Context<CreateAttester>,
+        attester_key: Pubkey,
+        achievements: Vec<Pubkey>,
+        all_achievements: bool,
+        daily_quota: u32,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        attester::instructions::create_attester(
+            ctx,
+            attester_key,
+            achievements,
+            all_achievements,
+            daily_quota,
+            expires_at,
+        )
+    }
+
+    pub fn update_attester(
+        ctx: Context<UpdateAttester>,
+        achievements: Vec<Pubkey>,
+        all_achievements: bool,
+        daily_quota: u32,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        attester::instructions::update_attester(
+            ctx,
+            achievements,
+            all_achievements,
+            daily_quota,
+            expires_at,
+        )
+    }
+
+    pub fn revoke_attester(ctx: Context<RevokeAttester>) -> Result<()> {
+        attester::instructions::revoke_attester(ctx)
+    }
+
     pub fn get_brand_info(ctx: Context<GetBrandInfo>) -> Result<BrandInfo> {
         brand::instructions::get_brand_info(ctx)
     }
@@ -73,6 +155,7 @@ pub mod community_engagement_protocol {
     }
 
     // Membership Instructions
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_membership(
         ctx: Context<InitializeMembership>,
         membership_id: u64,
@@ -105,8 +188,87 @@ pub mod community_engagement_protocol {
         duration: i64,
         is_open: bool,
         tier_uri: String,
+        max_supply: Option<u64>,
+        soulbound: bool,
     ) -> Result<()> {
-        membership::instructions::create_membership_tier(ctx, tier_id, duration, is_open, tier_uri)
+        membership::instructions::create_membership_tier(
+            ctx, tier_id, duration, is_open, tier_uri, max_supply, soulbound,
+        )
+    }
+
+    pub fn update_membership_tier(
+        ctx: Context<UpdateMembershipTier>,
+        tier_id: String,
+        duration: i64,
+        tier_uri: String,
+        max_supply: Option<u64>,
+    ) -> Result<()> {
+        membership::instructions::update_membership_tier(
+            ctx, tier_id, duration, tier_uri, max_supply,
+        )
+    }
+
+    pub fn set_tier_open(
+        ctx: Context<UpdateMembershipTier>,
+        tier_id: String,
+        is_open: bool,
+    ) -> Result<()> {
+        membership::instructions::set_tier_open(ctx, tier_id, is_open)
+    }
+
+    pub fn retire_membership_tier(
+        ctx: Context<UpdateMembershipTier>,
+        tier_id: String,
+    ) -> Result<()> {
+        membership::instructions::retire_membership_tier(ctx, tier_id)
+    }
+
+    pub fn add_to_tier_allowlist(
+        ctx: Context<AddToTierAllowlist>,
+        tier_id: String,
+        recipient: Pubkey,
+    ) -> Result<()> {
+        membership::instructions::add_to_tier_allowlist(ctx, tier_id, recipient)
+    }
+
+    pub fn remove_from_tier_allowlist(ctx: Context<RemoveFromTierAllowlist>) -> Result<()> {
+        membership::instructions::remove_from_tier_allowlist(ctx)
+    }
+
+    pub fn set_tier_allowlist_root(
+        ctx: Context<UpdateMembershipTier>,
+        tier_id: String,
+        allowlist_root: Option<[u8; 32]>,
+    ) -> Result<()> {
+        membership::instructions::set_tier_allowlist_root(ctx, tier_id, allowlist_root)
+    }
+
+    pub fn add_invite_code(
+        ctx: Context<AddInviteCode>,
+        tier_id: String,
+        code_hash: [u8; 32],
+    ) -> Result<()> {
+        membership::instructions::add_invite_code(ctx, tier_id, code_hash)
+    }
+
+    pub fn join_with_invite(
+        ctx: Context<JoinWithInvite>,
+        tier_id: String,
+        proof: Vec<[u8; 32]>,
+        code: Option<String>,
+    ) -> Result<()> {
+        membership::instructions::join_with_invite(ctx, tier_id, proof, code)
+    }
+
+    pub fn remove_membership_tier(
+        ctx: Context<RemoveMembershipTier>,
+        tier_id: String,
+    ) -> Result<()> {
+        membership::instructions::remove_membership_tier(ctx, tier_id)
+    }
+
+    pub fn close_membership(ctx: Context<CloseMembership>) -> Result<()> {
+        membership::instructions::close_membership(ctx)
     }
 
     // Achievement Instructions
@@ -120,6 +282,7 @@ pub mod community_engagement_protocol {
         achievement::instructions::create_achievement(ctx, name, description, criteria, points)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_fungible_achievement(
         ctx: Context<CreateFungibleAchievement>,
         name: String,
@@ -127,8 +290,19 @@ pub mod community_engagement_protocol {
         criteria: String,
         points: u32,
         supply: u64,
+        soulbound: bool,
+        token_metadata: Option<TokenMetadataArgs>,
     ) -> Result<()> {
-        achievement::create_fungible_achievement(ctx, name, description, criteria, points, supply)
+        achievement::create_fungible_achievement(
+            ctx,
+            name,
+            description,
+            criteria,
+            points,
+            supply,
+            soulbound,
+            token_metadata,
+        )
     }
 
     pub fn create_non_fungible_achievement(
@@ -138,6 +312,7 @@ pub mod community_engagement_protocol {
         criteria: String,
         points: u32,
         metadata_uri: String,
+        soulbound: bool,
     ) -> Result<()> {
         achievement::instructions::create_non_fungible_achievement(
             ctx,
@@ -146,6 +321,7 @@ pub mod community_engagement_protocol {
             criteria,
             points,
             metadata_uri,
+            soulbound,
         )
     }
 
@@ -157,6 +333,97 @@ pub mod community_engagement_protocol {
         achievement::instructions::award_non_fungible_achievement(ctx)
     }
 
+    pub fn batch_award_fungible_achievement<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchAwardFungibleAchievement<'info>>,
+    ) -> Result<Vec<bool>> {
+        achievement::instructions::batch_award_fungible_achievement(ctx)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_compressed_achievement(
+        ctx: Context<CreateCompressedAchievement>,
+        name: String,
+        description: String,
+        criteria: String,
+        points: u32,
+        metadata_uri: String,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        achievement::instructions::create_compressed_achievement(
+            ctx,
+            name,
+            description,
+            criteria,
+            points,
+            metadata_uri,
+            max_depth,
+            max_buffer_size,
+        )
+    }
+
+    pub fn award_compressed_achievement(ctx: Context<AwardCompressedAchievement>) -> Result<()> {
+        achievement::instructions::award_compressed_achievement(ctx)
+    }
+
+    pub fn update_achievement(
+        ctx: Context<UpdateAchievement>,
+        name: String,
+        description: String,
+        criteria: String,
+        points: u32,
+        metadata_uri: Option<String>,
+    ) -> Result<()> {
+        achievement::instructions::update_achievement(
+            ctx,
+            name,
+            description,
+            criteria,
+            points,
+            metadata_uri,
+        )
+    }
+
+    pub fn set_achievement_rules(
+        ctx: Context<UpdateAchievement>,
+        rules: Vec<ThresholdRule>,
+    ) -> Result<()> {
+        achievement::instructions::set_achievement_rules(ctx, rules)
+    }
+
+    pub fn set_achievement_prerequisites(
+        ctx: Context<UpdateAchievement>,
+        prerequisites: Vec<Pubkey>,
+    ) -> Result<()> {
+        achievement::instructions::set_achievement_prerequisites(ctx, prerequisites)
+    }
+
+    pub fn set_achievement_availability(
+        ctx: Context<UpdateAchievement>,
+        available_from: Option<i64>,
+        available_until: Option<i64>,
+        max_awards: Option<u64>,
+    ) -> Result<()> {
+        achievement::instructions::set_achievement_availability(
+            ctx,
+            available_from,
+            available_until,
+            max_awards,
+        )
+    }
+
+    pub fn record_progress(ctx: Context<RecordProgress>, metric: String, delta: u64) -> Result<()> {
+        progress::instructions::record_progress(ctx, metric, delta)
+    }
+
+    pub fn archive_achievement(ctx: Context<UpdateAchievement>) -> Result<()> {
+        achievement::instructions::archive_achievement(ctx)
+    }
+
+    pub fn close_achievement(ctx: Context<CloseAchievement>) -> Result<()> {
+        achievement::instructions::close_achievement(ctx)
+    }
+
     pub fn list_brand_achievements(ctx: Context<ListBrandAchievements>) -> Result<Vec<Pubkey>> {
         brand::instructions::list_brand_achievements(ctx)
     }
@@ -169,10 +436,168 @@ pub mod community_engagement_protocol {
         achievement::instructions::list_user_achievements(ctx)
     }
 
+    pub fn thaw_and_burn(ctx: Context<ThawAndBurn>, amount: u64) -> Result<()> {
+        achievement::instructions::thaw_and_burn(ctx, amount)
+    }
+
+    pub fn revoke_soulbound(ctx: Context<RevokeSoulbound>, amount: u64) -> Result<()> {
+        achievement::instructions::revoke_soulbound(ctx, amount)
+    }
+
     pub fn initialize_user_achievements(ctx: Context<InitializeUserAchievements>) -> Result<()> {
         achievement::instructions::initialize_user_achievements(ctx)
     }
 
+    pub fn create_quest(
+        ctx: Context<CreateQuest>,
+        name: String,
+        achievements: Vec<Pubkey>,
+        ordered: bool,
+        deadline: Option<i64>,
+        reward_amount: u64,
+        bonus_points: u64,
+    ) -> Result<()> {
+        quest::instructions::create_quest(
+            ctx,
+            name,
+            achievements,
+            ordered,
+            deadline,
+            reward_amount,
+            bonus_points,
+        )
+    }
+
+    pub fn complete_quest(ctx: Context<CompleteQuest>) -> Result<()> {
+        quest::instructions::complete_quest(ctx)
+    }
+
+    pub fn create_streak(
+        ctx: Context<CreateStreak>,
+        name: String,
+        period: i64,
+        grace: i64,
+        milestones: Vec<StreakMilestone>,
+    ) -> Result<()> {
+        streak::instructions::create_streak(ctx, name, period, grace, milestones)
+    }
+
+    pub fn record_streak_activity(ctx: Context<RecordStreakActivity>) -> Result<()> {
+        streak::instructions::record_streak_activity(ctx)
+    }
+
+    pub fn create_season(
+        ctx: Context<CreateSeason>,
+        season_id: u32,
+        starts_at: i64,
+        ends_at: i64,
+    ) -> Result<()> {
+        season::instructions::create_season(ctx, season_id, starts_at, ends_at)
+    }
+
+    pub fn close_season<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseSeason<'info>>,
+        rank_amounts: Vec<u64>,
+    ) -> Result<()> {
+        season::instructions::close_season(ctx, rank_amounts)
+    }
+
+    pub fn create_leaderboard(ctx: Context<CreateLeaderboard>) -> Result<()> {
+        leaderboard::instructions::create_leaderboard(ctx)
+    }
+
+    pub fn get_leaderboard(
+        ctx: Context<GetLeaderboard>,
+        offset: u16,
+        limit: u8,
+    ) -> Result<Vec<Standing>> {
+        leaderboard::instructions::get_leaderboard(ctx, offset, limit)
+    }
+
+    pub fn set_points_policy(ctx: Context<SetPointsPolicy>, expiry: PointExpiry) -> Result<()> {
+        points::instructions::set_points_policy(ctx, expiry)
+    }
+
+    pub fn get_points_balance(ctx: Context<GetPointsBalance>) -> Result<u64> {
+        points::instructions::get_points_balance(ctx)
+    }
+
+    pub fn redeem_points(ctx: Context<RedeemPoints>, amount: u64) -> Result<()> {
+        points::instructions::redeem_points(ctx, amount)
+    }
+
+    pub fn expire_points(ctx: Context<ExpirePoints>) -> Result<()> {
+        points::instructions::expire_points(ctx)
+    }
+
+    pub fn create_points_exchange(
+        ctx: Context<CreatePointsExchange>,
+        rate_bps: u32,
+        daily_cap: u64,
+    ) -> Result<()> {
+        points::instructions::create_points_exchange(ctx, rate_bps, daily_cap)
+    }
+
+    pub fn exchange_points(ctx: Context<ExchangePoints>, amount: u64) -> Result<()> {
+        points::instructions::exchange_points(ctx, amount)
+    }
+
+    pub fn configure_referrals(
+        ctx: Context<ConfigureReferrals>,
+        points: u64,
+        reward_amount: u64,
+        max_referrals: u32,
+    ) -> Result<()> {
+        referral::instructions::configure_referrals(ctx, points, reward_amount, max_referrals)
+    }
+
+    pub fn register_referral_code(ctx: Context<RegisterReferralCode>, code: String) -> Result<()> {
+        referral::instructions::register_referral_code(ctx, code)
+    }
+
+    pub fn credit_referral(ctx: Context<CreditReferral>) -> Result<()> {
+        referral::instructions::credit_referral(ctx)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_raffle(
+        ctx: Context<CreateRaffle>,
+        name: String,
+        requirement: EntryRequirement,
+        max_entries: u16,
+        prize_amount: u64,
+        winner_count: u8,
+        closes_at: i64,
+        oracle: Option<Pubkey>,
+    ) -> Result<()> {
+        raffle::instructions::create_raffle(
+            ctx,
+            name,
+            requirement,
+            max_entries,
+            prize_amount,
+            winner_count,
+            closes_at,
+            oracle,
+        )
+    }
+
+    pub fn enter_raffle(ctx: Context<EnterRaffle>) -> Result<()> {
+        raffle::instructions::enter_raffle(ctx)
+    }
+
+    pub fn schedule_raffle_draw(ctx: Context<ScheduleRaffleDraw>) -> Result<()> {
+        raffle::instructions::schedule_raffle_draw(ctx)
+    }
+
+    pub fn draw_raffle(ctx: Context<DrawRaffle>) -> Result<()> {
+        raffle::instructions::draw_raffle(ctx)
+    }
+
+    pub fn issue_raffle_prize(ctx: Context<IssueRafflePrize>) -> Result<()> {
+        raffle::instructions::issue_raffle_prize(ctx)
+    }
+
     pub fn create_fungible_reward(
         ctx: Context<CreateFungibleReward>,
         name: String,
@@ -186,19 +611,62 @@ pub mod community_engagement_protocol {
         reward::instructions::issue_fungible_reward(ctx, amount)
     }
 
+    pub fn batch_issue_fungible_reward<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchIssueFungibleReward<'info>>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        reward::instructions::batch_issue_fungible_reward(ctx, amounts)
+    }
+
     pub fn create_non_fungible_reward(
         ctx: Context<CreateNonFungibleReward>,
         name: String,
         description: String,
         metadata_uri: String,
+        token_metadata: Option<TokenMetadataArgs>,
     ) -> Result<()> {
-        reward::instructions::create_non_fungible_reward(ctx, name, description, metadata_uri)
+        reward::instructions::create_non_fungible_reward(
+            ctx,
+            name,
+            description,
+            metadata_uri,
+            token_metadata,
+        )
     }
 
     pub fn issue_non_fungible_reward(ctx: Context<IssueNonFungibleReward>) -> Result<()> {
         reward::instructions::issue_non_fungible_reward(ctx)
     }
 
+    pub fn update_reward(
+        ctx: Context<UpdateRewardDetails>,
+        name: String,
+        description: String,
+        metadata_uri: Option<String>,
+    ) -> Result<()> {
+        reward::instructions::update_reward(ctx, name, description, metadata_uri)
+    }
+
+    pub fn set_reward_active(ctx: Context<UpdateReward>, active: bool) -> Result<()> {
+        reward::instructions::set_reward_active(ctx, active)
+    }
+
+    pub fn set_reward_schedule(
+        ctx: Context<UpdateReward>,
+        starts_at: Option<i64>,
+        ends_at: Option<i64>,
+    ) -> Result<()> {
+        reward::instructions::set_reward_schedule(ctx, starts_at, ends_at)
+    }
+
+    pub fn close_reward(ctx: Context<CloseReward>) -> Result<()> {
+        reward::instructions::close_reward(ctx)
+    }
+
+    pub fn close_reward_instance(ctx: Context<CloseRewardInstance>) -> Result<()> {
+        reward::instructions::close_reward_instance(ctx)
+    }
+
     pub fn update_tronic_admin(ctx: Context<UpdateTronicAdmin>, new_admin: Pubkey) -> Result<()> {
         let program_state = &mut ctx.accounts.program_state;
         program_state.tronic_admin = new_admin;
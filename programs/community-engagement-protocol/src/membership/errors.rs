@@ -14,4 +14,26 @@ pub enum MembershipError {
     MaxTiersReached,
     #[msg("Invalid brand")]
     InvalidBrand,
+    #[msg("Tier not found")]
+    TierNotFound,
+    #[msg("Tier has been retired")]
+    TierRetired,
+    #[msg("Tier supply reached")]
+    TierSupplyReached,
+    #[msg("Recipient is not on the allowlist for this closed tier")]
+    NotOnAllowlist,
+    #[msg("Invalid allowlist proof or invite code")]
+    InvalidInviteProof,
+    #[msg("Invite code has already been used")]
+    InviteCodeConsumed,
+    #[msg("Metadata, master edition and token metadata program are required for SPL Token memberships")]
+    MetadataAccountsRequired,
+    #[msg("Tier has minted memberships and can only be retired")]
+    TierHasMemberships,
+    #[msg("Tier id must be 10 bytes or less")]
+    TierIdTooLong,
+    #[msg("Memberships can have at most 10 tiers")]
+    TooManyTiers,
+    #[msg("Tier supply can't be lower than the memberships already minted")]
+    TierSupplyBelowMinted,
 }
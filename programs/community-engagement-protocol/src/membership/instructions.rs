@@ -19,7 +19,7 @@ pub struct InitializeMembership<'info> {
         8 +  // total_minted
         8 +  // total_burned
         32 +  // admin (Pubkey)
-        (4 + 10 + 8 + 1 + 4 + 200) * 10  // tiers (4 bytes for length prefix + max 10 bytes for tier_id + 8 bytes for duration + 1 byte for is_open + 4 bytes for length prefix + max 200 bytes for tier_uri) * max 10 tiers
+        (4 + 10 + 8 + 1 + 4 + 200 + 1 + 9 + 8 + 33 + 1) * 10  // tiers (4 bytes for length prefix + max 10 bytes for tier_id + 8 bytes for duration + 1 byte for is_open + 4 bytes for length prefix + max 200 bytes for tier_uri + 1 byte for retired + 9 bytes for max_supply + 8 bytes for minted + 33 bytes for allowlist_root + 1 byte for soulbound) * max 10 tiers
     )]
     pub membership_data: Account<'info, MembershipData>,
     #[account(
@@ -33,7 +33,7 @@ pub struct InitializeMembership<'info> {
     pub system_program: Program<'info, System>,
 }
 
-
+#[allow(clippy::too_many_arguments)]
 pub fn initialize_membership(
     ctx: Context<InitializeMembership>,
     membership_id: u64,
@@ -44,9 +44,14 @@ pub fn initialize_membership(
     is_elastic: bool,
     max_tiers: u8,
 ) -> Result<()> {
+    require!(
+        usize::from(max_tiers) <= MembershipData::MAX_TIERS,
+        MembershipError::TooManyTiers
+    );
+
     let membership_data = &mut ctx.accounts.membership_data;
     let brand = &mut ctx.accounts.brand;
-    
+
     membership_data.brand = brand.key();
     membership_data.membership_id = membership_id;
     membership_data.name = name;
@@ -69,23 +74,33 @@ pub fn initialize_membership(
 pub struct MintMembership<'info> {
     #[account(mut)]
     pub membership_data: Account<'info, MembershipData>,
+    /// Created in the instruction so it can be owned by either token program
+    #[account(mut)]
+    pub mint: Signer<'info>,
+    /// CHECK: The recipient's associated token account, created in the instruction
     #[account(
-        init,
-        payer = tronic_admin,
-        mint::decimals = 0,
-        mint::authority = tronic_admin.key(),
-        mint::freeze_authority = tronic_admin.key(),
+        mut,
+        seeds = [recipient.key().as_ref(), token_program.key().as_ref(), mint.key().as_ref()],
+        bump,
+        seeds::program = associated_token_program.key(),
     )]
-    pub mint: Account<'info, Mint>,
+    pub token_account: UncheckedAccount<'info>,
     #[account(
-        init_if_needed,
+        init,
         payer = tronic_admin,
-        associated_token::mint = mint,
-        associated_token::authority = recipient,
+        space = MembershipToken::SPACE,
+        seeds = [b"membership-token", mint.key().as_ref()],
+        bump
     )]
-    pub token_account: Account<'info, TokenAccount>,
-    /// CHECK: This is the account that will receive the minted token
+    pub membership_token: Account<'info, MembershipToken>,
+    /// CHECK: This is the account that will receive the minted token. Signs
+    /// for soulbound SPL Token tiers to approve the brand's mint authority as
+    /// delegate
     pub recipient: UncheckedAccount<'info>,
+    /// CHECK: The brand's PDA that freezes soulbound memberships as the
+    /// holder's delegate, or as the permanent delegate of Token-2022 ones
+    #[account(seeds = [MINT_AUTHORITY_SEED, membership_data.brand.as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
     #[account(
         seeds = [b"program-state"],
         bump,
@@ -94,15 +109,24 @@ pub struct MintMembership<'info> {
     pub program_state: Account<'info, ProgramState>,
     #[account(mut)]
     pub tronic_admin: Signer<'info>,
-    /// CHECK: This is the metadata account that will be created
+    /// CHECK: This is the metadata account that will be created (SPL Token only)
+    #[account(mut)]
+    pub metadata: Option<UncheckedAccount<'info>>,
+    /// CHECK: This is the master edition account that will be created (SPL Token only)
+    #[account(mut)]
+    pub master_edition: Option<UncheckedAccount<'info>>,
+    /// Required when minting from a tier that is not open
+    pub allowlist_entry: Option<Account<'info, TierAllowlistEntry>>,
+    /// Code of the member who referred the recipient
     #[account(mut)]
-    pub metadata: UncheckedAccount<'info>,
-    /// CHECK: This is the master edition account that will be created
+    pub referral_code: Option<Account<'info, ReferralCode>>,
+    /// CHECK: The recipient's `[b"referral", brand, recipient]` PDA, required
+    /// with `referral_code` and created in the instruction
     #[account(mut)]
-    pub master_edition: UncheckedAccount<'info>,
-    pub token_program: Program<'info, Token>,
+    pub referral: Option<UncheckedAccount<'info>>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_metadata_program: Program<'info, Metadata>,
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -115,11 +139,121 @@ pub fn mint_membership(ctx: Context<MintMembership>, tier_index: u8) -> Result<(
         MembershipError::MaxSupplyReached
     );
 
+    let tier = membership_data
+        .tiers
+        .get(tier_index as usize)
+        .ok_or(MembershipError::InvalidTierIndex)?;
+    require!(!tier.retired, MembershipError::TierRetired);
+    if let Some(max_supply) = tier.max_supply {
+        require!(tier.minted < max_supply, MembershipError::TierSupplyReached);
+    }
+
+    // Closed tiers can only be minted to allowlisted recipients
+    if !tier.is_open {
+        let entry = ctx
+            .accounts
+            .allowlist_entry
+            .as_ref()
+            .ok_or(MembershipError::NotOnAllowlist)?;
+        require!(
+            entry.membership_data == membership_data.key()
+                && entry.tier_id == tier.tier_id
+                && entry.recipient == ctx.accounts.recipient.key(),
+            MembershipError::NotOnAllowlist
+        );
+    }
+
+    let membership_token = &mut ctx.accounts.membership_token;
+    membership_token.membership_data = membership_data.key();
+    membership_token.mint = ctx.accounts.mint.key();
+    membership_token.tier_id = tier.tier_id.clone();
+    membership_token.minted_at = Clock::get()?.unix_timestamp;
+
+    // New members can name the referrer whose code brought them in
+    match (
+        ctx.accounts.referral_code.as_mut(),
+        ctx.accounts.referral.as_ref(),
+    ) {
+        (None, None) => {}
+        (Some(referral_code), Some(referral_info)) => {
+            let recipient = ctx.accounts.recipient.key();
+            require_keys_eq!(
+                referral_code.brand,
+                membership_data.brand,
+                CepError::InvalidReferral
+            );
+            require_keys_neq!(referral_code.referrer, recipient, CepError::SelfReferral);
+            // Members are only ever referred once per brand
+            require!(referral_info.data_is_empty(), CepError::InvalidReferral);
+            referral_code.referred_count += 1;
+
+            let mut referral: Referral = load_or_create_account(
+                referral_info,
+                &[
+                    b"referral",
+                    membership_data.brand.as_ref(),
+                    recipient.as_ref(),
+                ],
+                Referral::SPACE,
+                &ctx.accounts.tronic_admin,
+                &ctx.accounts.system_program,
+            )?;
+            referral.brand = membership_data.brand;
+            referral.referrer = referral_code.referrer;
+            referral.referred = recipient;
+            referral.code = referral_code.key();
+            referral.credited = false;
+            referral.recorded_at = Clock::get()?.unix_timestamp;
+            store_account(referral_info, &referral)?;
+        }
+        _ => return Err(CepError::InvalidReferral.into()),
+    }
+
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let token_2022 = is_token_2022(&token_program);
+    let uri = format!("{}{}", membership_data.base_uri, tier.tier_uri);
+
+    // Token-2022 memberships keep their metadata on the mint and are made
+    // soulbound with the NonTransferable extension
+    NewMint {
+        token_program: &token_program,
+        system_program: &ctx.accounts.system_program.to_account_info(),
+        payer: &ctx.accounts.tronic_admin.to_account_info(),
+        mint: &ctx.accounts.mint.to_account_info(),
+        authority: &ctx.accounts.tronic_admin.to_account_info(),
+        signer_seeds: &[],
+    }
+    .create(
+        0,
+        token_2022.then(|| {
+            (
+                membership_data.name.clone(),
+                TokenMetadataArgs {
+                    symbol: membership_data.symbol.clone(),
+                    uri: uri.clone(),
+                },
+            )
+        }),
+        (token_2022 && tier.soulbound).then(|| ctx.accounts.mint_authority.key()),
+    )?;
+
+    create(CpiContext::new(
+        ctx.accounts.associated_token_program.to_account_info(),
+        Create {
+            payer: ctx.accounts.tronic_admin.to_account_info(),
+            associated_token: ctx.accounts.token_account.to_account_info(),
+            authority: ctx.accounts.recipient.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: token_program.clone(),
+        },
+    ))?;
+
     // Mint the NFT
-    anchor_spl::token::mint_to(
+    mint_to(
         CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            anchor_spl::token::MintTo {
+            token_program.clone(),
+            MintTo {
                 mint: ctx.accounts.mint.to_account_info(),
                 to: ctx.accounts.token_account.to_account_info(),
                 authority: ctx.accounts.tronic_admin.to_account_info(),
@@ -128,10 +262,38 @@ pub fn mint_membership(ctx: Context<MintMembership>, tier_index: u8) -> Result<(
         1,
     )?;
 
+    if token_2022 {
+        // Without a master edition, revoking the mint authority is what
+        // keeps the supply at one
+        set_authority(
+            CpiContext::new(
+                token_program,
+                SetAuthority {
+                    current_authority: ctx.accounts.tronic_admin.to_account_info(),
+                    account_or_mint: ctx.accounts.mint.to_account_info(),
+                },
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+
+        membership_data.total_minted += 1;
+        membership_data.tiers[tier_index as usize].minted += 1;
+
+        return Ok(());
+    }
+
+    let (Some(metadata), Some(master_edition), Some(token_metadata_program)) = (
+        &ctx.accounts.metadata,
+        &ctx.accounts.master_edition,
+        &ctx.accounts.token_metadata_program,
+    ) else {
+        return Err(MembershipError::MetadataAccountsRequired.into());
+    };
+
     // Create metadata
-    let tier = &membership_data.tiers[tier_index as usize];
     let metadata_accounts = CreateMetadataAccountsV3 {
-        metadata: ctx.accounts.metadata.to_account_info(),
+        metadata: metadata.to_account_info(),
         mint: ctx.accounts.mint.to_account_info(),
         mint_authority: ctx.accounts.tronic_admin.to_account_info(),
         payer: ctx.accounts.tronic_admin.to_account_info(),
@@ -143,7 +305,7 @@ pub fn mint_membership(ctx: Context<MintMembership>, tier_index: u8) -> Result<(
     let data = DataV2 {
         name: membership_data.name.clone(),
         symbol: membership_data.symbol.clone(),
-        uri: format!("{}{}", membership_data.base_uri, tier.tier_uri),
+        uri,
         seller_fee_basis_points: 0,
         creators: None,
         collection: None,
@@ -151,10 +313,7 @@ pub fn mint_membership(ctx: Context<MintMembership>, tier_index: u8) -> Result<(
     };
 
     create_metadata_accounts_v3(
-        CpiContext::new(
-            ctx.accounts.token_metadata_program.to_account_info(),
-            metadata_accounts,
-        ),
+        CpiContext::new(token_metadata_program.to_account_info(), metadata_accounts),
         data,
         true,
         true,
@@ -163,12 +322,12 @@ pub fn mint_membership(ctx: Context<MintMembership>, tier_index: u8) -> Result<(
 
     // Create master edition
     let master_edition_accounts = CreateMasterEditionV3 {
-        edition: ctx.accounts.master_edition.to_account_info(),
+        edition: master_edition.to_account_info(),
         mint: ctx.accounts.mint.to_account_info(),
         update_authority: ctx.accounts.tronic_admin.to_account_info(),
         mint_authority: ctx.accounts.tronic_admin.to_account_info(),
         payer: ctx.accounts.tronic_admin.to_account_info(),
-        metadata: ctx.accounts.metadata.to_account_info(),
+        metadata: metadata.to_account_info(),
         token_program: ctx.accounts.token_program.to_account_info(),
         system_program: ctx.accounts.system_program.to_account_info(),
         rent: ctx.accounts.rent.to_account_info(),
@@ -176,14 +335,36 @@ pub fn mint_membership(ctx: Context<MintMembership>, tier_index: u8) -> Result<(
 
     create_master_edition_v3(
         CpiContext::new(
-            ctx.accounts.token_metadata_program.to_account_info(),
+            token_metadata_program.to_account_info(),
             master_edition_accounts,
         ),
         Some(1), // Max supply of 1 for NFT
     )?;
 
+    // The master edition now holds the freeze authority, so soulbound
+    // memberships are frozen through Token Metadata by the brand's mint
+    // authority as the recipient's delegate
+    if tier.soulbound {
+        freeze_soulbound_edition(
+            token_metadata_program,
+            &ctx.accounts.token_program,
+            &ctx.accounts.token_account,
+            &ctx.accounts.mint,
+            master_edition,
+            &ctx.accounts.recipient.to_account_info(),
+            &ctx.accounts.mint_authority,
+            1,
+            &[
+                MINT_AUTHORITY_SEED,
+                membership_data.brand.as_ref(),
+                &[ctx.bumps.mint_authority],
+            ],
+        )?;
+    }
+
     // Update membership data
     membership_data.total_minted += 1;
+    membership_data.tiers[tier_index as usize].minted += 1;
 
     Ok(())
 }
@@ -211,7 +392,18 @@ pub fn create_membership_tier(
     duration: i64,
     is_open: bool,
     tier_uri: String,
+    max_supply: Option<u64>,
+    soulbound: bool,
 ) -> Result<()> {
+    require!(
+        tier_id.len() <= MembershipTier::MAX_ID_LENGTH,
+        MembershipError::TierIdTooLong
+    );
+    require!(
+        tier_uri.len() <= MembershipTier::MAX_URI_LENGTH,
+        CepError::UriTooLong
+    );
+
     let membership_data = &mut ctx.accounts.membership_data;
     let brand = &ctx.accounts.brand;
 
@@ -235,7 +427,333 @@ pub fn create_membership_tier(
         duration,
         is_open,
         tier_uri,
+        retired: false,
+        max_supply,
+        minted: 0,
+        allowlist_root: None,
+        soulbound,
     });
 
     Ok(())
 }
+
+#[derive(Accounts)]
+pub struct UpdateMembershipTier<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(mut, has_one = brand @ MembershipError::InvalidBrand)]
+    pub membership_data: Account<'info, MembershipData>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    pub authority: Signer<'info>,
+}
+
+pub fn update_membership_tier(
+    ctx: Context<UpdateMembershipTier>,
+    tier_id: String,
+    duration: i64,
+    tier_uri: String,
+    max_supply: Option<u64>,
+) -> Result<()> {
+    require!(
+        tier_id.len() <= MembershipTier::MAX_ID_LENGTH,
+        MembershipError::TierIdTooLong
+    );
+    require!(
+        tier_uri.len() <= MembershipTier::MAX_URI_LENGTH,
+        CepError::UriTooLong
+    );
+
+    let tier = ctx.accounts.membership_data.tier_mut(&tier_id)?;
+    // The cap can't drop below the memberships already minted from the tier
+    if let Some(max_supply) = max_supply {
+        require!(
+            max_supply >= tier.minted,
+            MembershipError::TierSupplyBelowMinted
+        );
+    }
+    tier.duration = duration;
+    tier.tier_uri = tier_uri;
+    tier.max_supply = max_supply;
+
+    Ok(())
+}
+
+pub fn set_tier_open(
+    ctx: Context<UpdateMembershipTier>,
+    tier_id: String,
+    is_open: bool,
+) -> Result<()> {
+    let tier = ctx.accounts.membership_data.tier_mut(&tier_id)?;
+    tier.is_open = is_open;
+
+    Ok(())
+}
+
+pub fn retire_membership_tier(ctx: Context<UpdateMembershipTier>, tier_id: String) -> Result<()> {
+    // The tier stays in place so existing holders keep their tier index
+    let tier = ctx.accounts.membership_data.tier_mut(&tier_id)?;
+    tier.retired = true;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveMembershipTier<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(mut, has_one = brand @ MembershipError::InvalidBrand)]
+    pub membership_data: Account<'info, MembershipData>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    pub authority: Signer<'info>,
+}
+
+pub fn remove_membership_tier(ctx: Context<RemoveMembershipTier>, tier_id: String) -> Result<()> {
+    let membership_data = &mut ctx.accounts.membership_data;
+
+    let position = membership_data
+        .tiers
+        .iter()
+        .position(|tier| tier.tier_id == tier_id)
+        .ok_or(MembershipError::TierNotFound)?;
+    // Removing shifts every later tier index, so only never-minted tiers may go
+    require!(
+        membership_data.tiers[position].minted == 0,
+        MembershipError::TierHasMemberships
+    );
+    membership_data.tiers.remove(position);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseMembership<'info> {
+    #[account(
+        mut,
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(mut, has_one = brand @ MembershipError::InvalidBrand, close = recipient)]
+    pub membership_data: Account<'info, MembershipData>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    pub authority: Signer<'info>,
+    /// CHECK: Only receives the reclaimed rent
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+pub fn close_membership(ctx: Context<CloseMembership>) -> Result<()> {
+    let membership_key = ctx.accounts.membership_data.key();
+    let brand = &mut ctx.accounts.brand;
+
+    // Unlink the membership from the brand
+    brand.memberships.retain(|m| *m != membership_key);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(tier_id: String, recipient: Pubkey)]
+pub struct AddToTierAllowlist<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(has_one = brand @ MembershipError::InvalidBrand)]
+    pub membership_data: Account<'info, MembershipData>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + (4 + 10) + 32, // discriminator + membership_data + tier_id + recipient
+        seeds = [
+            b"tier-allowlist",
+            membership_data.key().as_ref(),
+            tier_id.as_bytes(),
+            recipient.as_ref()
+        ],
+        bump
+    )]
+    pub allowlist_entry: Account<'info, TierAllowlistEntry>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn add_to_tier_allowlist(
+    ctx: Context<AddToTierAllowlist>,
+    tier_id: String,
+    recipient: Pubkey,
+) -> Result<()> {
+    ctx.accounts.membership_data.tier(&tier_id)?;
+
+    let entry = &mut ctx.accounts.allowlist_entry;
+    entry.membership_data = ctx.accounts.membership_data.key();
+    entry.tier_id = tier_id;
+    entry.recipient = recipient;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveFromTierAllowlist<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(has_one = brand @ MembershipError::InvalidBrand)]
+    pub membership_data: Account<'info, MembershipData>,
+    #[account(mut, has_one = membership_data, close = authority)]
+    pub allowlist_entry: Account<'info, TierAllowlistEntry>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn remove_from_tier_allowlist(_ctx: Context<RemoveFromTierAllowlist>) -> Result<()> {
+    Ok(())
+}
+
+pub fn set_tier_allowlist_root(
+    ctx: Context<UpdateMembershipTier>,
+    tier_id: String,
+    allowlist_root: Option<[u8; 32]>,
+) -> Result<()> {
+    let tier = ctx.accounts.membership_data.tier_mut(&tier_id)?;
+    tier.allowlist_root = allowlist_root;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(tier_id: String, code_hash: [u8; 32])]
+pub struct AddInviteCode<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(has_one = brand @ MembershipError::InvalidBrand)]
+    pub membership_data: Account<'info, MembershipData>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + (4 + 10) + 32 + 1 + (1 + 32), // discriminator + membership_data + tier_id + code_hash + consumed + consumed_by
+        seeds = [b"invite-code", membership_data.key().as_ref(), code_hash.as_ref()],
+        bump
+    )]
+    pub invite_code: Account<'info, InviteCode>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn add_invite_code(
+    ctx: Context<AddInviteCode>,
+    tier_id: String,
+    code_hash: [u8; 32],
+) -> Result<()> {
+    ctx.accounts.membership_data.tier(&tier_id)?;
+
+    let invite_code = &mut ctx.accounts.invite_code;
+    invite_code.membership_data = ctx.accounts.membership_data.key();
+    invite_code.tier_id = tier_id;
+    invite_code.code_hash = code_hash;
+    invite_code.consumed = false;
+    invite_code.consumed_by = None;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(tier_id: String)]
+pub struct JoinWithInvite<'info> {
+    pub membership_data: Account<'info, MembershipData>,
+    /// Required when joining with an invite code rather than a Merkle proof
+    #[account(mut)]
+    pub invite_code: Option<Account<'info, InviteCode>>,
+    #[account(
+        init,
+        payer = recipient,
+        space = 8 + 32 + (4 + 10) + 32, // discriminator + membership_data + tier_id + recipient
+        seeds = [
+            b"tier-allowlist",
+            membership_data.key().as_ref(),
+            tier_id.as_bytes(),
+            recipient.key().as_ref()
+        ],
+        bump
+    )]
+    pub allowlist_entry: Account<'info, TierAllowlistEntry>,
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Lets a recipient put themselves on a closed tier's allowlist, either by
+/// proving they are in the tier's Merkle allowlist or by revealing an unused
+/// invite code. The resulting entry is what `mint_membership` checks.
+pub fn join_with_invite(
+    ctx: Context<JoinWithInvite>,
+    tier_id: String,
+    proof: Vec<[u8; 32]>,
+    code: Option<String>,
+) -> Result<()> {
+    let membership_data = &ctx.accounts.membership_data;
+    let recipient = ctx.accounts.recipient.key();
+    let tier = membership_data.tier(&tier_id)?;
+
+    if let Some(code) = code {
+        let invite_code = ctx
+            .accounts
+            .invite_code
+            .as_mut()
+            .ok_or(MembershipError::InvalidInviteProof)?;
+        require!(
+            invite_code.membership_data == membership_data.key()
+                && invite_code.tier_id == tier_id
+                && invite_code.code_hash == hash(code.as_bytes()).to_bytes(),
+            MembershipError::InvalidInviteProof
+        );
+        require!(!invite_code.consumed, MembershipError::InviteCodeConsumed);
+
+        invite_code.consumed = true;
+        invite_code.consumed_by = Some(recipient);
+    } else {
+        let root = tier
+            .allowlist_root
+            .ok_or(MembershipError::InvalidInviteProof)?;
+        require!(
+            verify_merkle_proof(&proof, root, hash(recipient.as_ref()).to_bytes()),
+            MembershipError::InvalidInviteProof
+        );
+    }
+
+    let entry = &mut ctx.accounts.allowlist_entry;
+    entry.membership_data = membership_data.key();
+    entry.tier_id = tier_id;
+    entry.recipient = recipient;
+
+    Ok(())
+}
+
+/// Verifies a Merkle proof built with sorted-pair SHA-256 hashing.
+fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+    computed == root
+}
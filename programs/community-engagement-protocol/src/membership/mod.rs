@@ -1,15 +1,23 @@
+use crate::achievement::instructions::freeze_soulbound_edition;
+use crate::achievement::state::MINT_AUTHORITY_SEED;
+use crate::referral::state::{Referral, ReferralCode};
+use crate::token_extensions::{is_token_2022, NewMint, TokenMetadataArgs};
+use crate::utils::{load_or_create_account, store_account};
 use anchor_lang::prelude::*;
-use anchor_spl::associated_token::AssociatedToken;
+use anchor_lang::solana_program::hash::{hash, hashv};
+use anchor_spl::associated_token::{create, AssociatedToken, Create};
 use anchor_spl::metadata::{
     create_master_edition_v3, create_metadata_accounts_v3, CreateMasterEditionV3,
     CreateMetadataAccountsV3, Metadata,
 };
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{
+    mint_to, set_authority, spl_token_2022::instruction::AuthorityType, MintTo, SetAuthority,
+    TokenInterface,
+};
 
 pub mod errors;
 pub mod instructions;
 pub mod state;
 
 pub use errors::MembershipError;
-use instructions::*;
 use state::*;
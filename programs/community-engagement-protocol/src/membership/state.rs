@@ -1,3 +1,4 @@
+use super::errors::MembershipError;
 use anchor_lang::prelude::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
@@ -6,6 +7,16 @@ pub struct MembershipTier {
     pub duration: i64,
     pub is_open: bool,
     pub tier_uri: String,
+    pub retired: bool,
+    pub max_supply: Option<u64>,
+    pub minted: u64,
+    pub allowlist_root: Option<[u8; 32]>,
+    pub soulbound: bool,
+}
+
+impl MembershipTier {
+    pub const MAX_ID_LENGTH: usize = 10;
+    pub const MAX_URI_LENGTH: usize = 200;
 }
 
 #[account]
@@ -22,3 +33,57 @@ pub struct MembershipData {
     pub total_burned: u64,
     pub tiers: Vec<MembershipTier>,
 }
+
+/// Ties a membership token's mint to the membership and tier it was minted from.
+#[account]
+pub struct MembershipToken {
+    pub membership_data: Pubkey,
+    pub mint: Pubkey,
+    pub tier_id: String,
+    pub minted_at: i64,
+}
+
+impl MembershipToken {
+    pub const SPACE: usize = 8 // Discriminator
+        + 32 // Membership data
+        + 32 // Mint
+        + 4 + MembershipTier::MAX_ID_LENGTH // Tier id
+        + 8; // Minted at
+}
+
+/// Lets a specific recipient mint from a tier that is not open.
+#[account]
+pub struct TierAllowlistEntry {
+    pub membership_data: Pubkey,
+    pub tier_id: String,
+    pub recipient: Pubkey,
+}
+
+/// A single-use invite code for a tier, stored as the hash of the code.
+#[account]
+pub struct InviteCode {
+    pub membership_data: Pubkey,
+    pub tier_id: String,
+    pub code_hash: [u8; 32],
+    pub consumed: bool,
+    pub consumed_by: Option<Pubkey>,
+}
+
+impl MembershipData {
+    /// Tiers the account is sized for
+    pub const MAX_TIERS: usize = 10;
+
+    pub fn tier(&self, tier_id: &str) -> Result<&MembershipTier> {
+        self.tiers
+            .iter()
+            .find(|tier| tier.tier_id == tier_id)
+            .ok_or(MembershipError::TierNotFound.into())
+    }
+
+    pub fn tier_mut(&mut self, tier_id: &str) -> Result<&mut MembershipTier> {
+        self.tiers
+            .iter_mut()
+            .find(|tier| tier.tier_id == tier_id)
+            .ok_or(MembershipError::TierNotFound.into())
+    }
+}
@@ -0,0 +1,255 @@
+use super::state::*;
+use crate::attester::state::{authorize_activity, Attester};
+use crate::brand::state::Brand;
+use crate::errors::CepError;
+use crate::ProgramState;
+use anchor_lang::prelude::*;
+
+pub fn set_points_policy(ctx: Context<SetPointsPolicy>, expiry: PointExpiry) -> Result<()> {
+    if let PointExpiry::Fixed { days } | PointExpiry::LinearDecay { days } = expiry {
+        require!(days > 0, CepError::InvalidPointsPolicy);
+    }
+
+    let policy = &mut ctx.accounts.points_policy;
+    policy.brand = ctx.accounts.brand.key();
+    policy.expiry = expiry;
+    policy.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Points policy updated for Brand '{}'",
+        ctx.accounts.brand.name
+    );
+    Ok(())
+}
+
+/// Returns the points the user can spend right now under the brand's policy,
+/// if it has one.
+pub fn get_points_balance(ctx: Context<GetPointsBalance>) -> Result<u64> {
+    Ok(ctx.accounts.user_points.available(
+        &PointsPolicy::expiry(ctx.accounts.points_policy.as_deref()),
+        Clock::get()?.unix_timestamp,
+    ))
+}
+
+/// Spends the user's points with the brand. The user signs, along with a
+/// brand authority or attester accepting the redemption.
+pub fn redeem_points(ctx: Context<RedeemPoints>, amount: u64) -> Result<()> {
+    authorize_activity(
+        &ctx.accounts.brand,
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.program_state.tronic_admin,
+        ctx.accounts.attester.as_ref(),
+    )?;
+
+    let user_points = &mut ctx.accounts.user_points;
+    user_points.debit(
+        amount,
+        &PointsPolicy::expiry(ctx.accounts.points_policy.as_deref()),
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    msg!(
+        "User {} redeemed {} points with Brand '{}'",
+        user_points.user,
+        amount,
+        ctx.accounts.brand.name
+    );
+    Ok(())
+}
+
+/// Drops the user's expired points. Anyone can crank this.
+pub fn expire_points(ctx: Context<ExpirePoints>) -> Result<()> {
+    let user_points = &mut ctx.accounts.user_points;
+    let expired = user_points.expire(
+        &PointsPolicy::expiry(ctx.accounts.points_policy.as_deref()),
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    msg!("Expired {} points of user {}", expired, user_points.user);
+    Ok(())
+}
+
+pub fn create_points_exchange(
+    ctx: Context<CreatePointsExchange>,
+    rate_bps: u32,
+    daily_cap: u64,
+) -> Result<()> {
+    require!(
+        ctx.accounts.from_brand.key() != ctx.accounts.to_brand.key() && rate_bps > 0,
+        CepError::InvalidPointsExchange
+    );
+
+    let exchange = &mut ctx.accounts.points_exchange;
+    let clock = Clock::get()?;
+    exchange.from_brand = ctx.accounts.from_brand.key();
+    exchange.to_brand = ctx.accounts.to_brand.key();
+    exchange.rate_bps = rate_bps;
+    exchange.daily_cap = daily_cap;
+    exchange.current_day = 0;
+    exchange.exchanged_today = 0;
+    exchange.created_at = clock.unix_timestamp;
+
+    msg!(
+        "Points of Brand '{}' exchange into Brand '{}' at {} bps",
+        ctx.accounts.from_brand.name,
+        ctx.accounts.to_brand.name,
+        rate_bps
+    );
+    Ok(())
+}
+
+/// Converts `amount` of the user's available `from_brand` points into
+/// `to_brand` points at the exchange's rate, within its daily cap.
+pub fn exchange_points(ctx: Context<ExchangePoints>, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let user = ctx.accounts.user.key();
+
+    ctx.accounts.from_user_points.debit(
+        amount,
+        &PointsPolicy::expiry(ctx.accounts.from_points_policy.as_deref()),
+        clock.unix_timestamp,
+    )?;
+    let credited = ctx
+        .accounts
+        .points_exchange
+        .exchange(amount, clock.unix_timestamp)?;
+
+    credit_award_points(
+        ctx.accounts.to_brand.key(),
+        user,
+        credited,
+        clock.unix_timestamp,
+        &mut ctx.accounts.to_user_points,
+        &ctx.accounts.to_leaderboard,
+        None,
+    )?;
+
+    msg!(
+        "User {} exchanged {} points of Brand '{}' for {} points of Brand '{}'",
+        user,
+        amount,
+        ctx.accounts.from_brand.name,
+        credited,
+        ctx.accounts.to_brand.name
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPointsPolicy<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PointsPolicy::SPACE,
+        seeds = [b"points-policy", brand.key().as_ref()],
+        bump
+    )]
+    pub points_policy: Account<'info, PointsPolicy>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetPointsBalance<'info> {
+    pub user_points: Account<'info, UserPoints>,
+    /// Left out when the brand never set a points policy
+    #[account(seeds = [b"points-policy", user_points.brand.as_ref()], bump)]
+    pub points_policy: Option<Account<'info, PointsPolicy>>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemPoints<'info> {
+    pub brand: Account<'info, Brand>,
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"user-points", brand.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_points: Account<'info, UserPoints>,
+    /// Left out when the brand never set a points policy
+    #[account(seeds = [b"points-policy", brand.key().as_ref()], bump)]
+    pub points_policy: Option<Account<'info, PointsPolicy>>,
+    pub authority: Signer<'info>,
+    /// Required when `authority` accepts the redemption as a delegated attester
+    pub attester: Option<Account<'info, Attester>>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+#[derive(Accounts)]
+pub struct ExpirePoints<'info> {
+    #[account(mut)]
+    pub user_points: Account<'info, UserPoints>,
+    /// Left out when the brand never set a points policy
+    #[account(seeds = [b"points-policy", user_points.brand.as_ref()], bump)]
+    pub points_policy: Option<Account<'info, PointsPolicy>>,
+}
+
+#[derive(Accounts)]
+pub struct CreatePointsExchange<'info> {
+    #[account(
+        constraint = from_brand.is_authority(&from_authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub from_brand: Account<'info, Brand>,
+    #[account(
+        constraint = to_brand.is_authority(&to_authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub to_brand: Account<'info, Brand>,
+    #[account(
+        init,
+        payer = from_authority,
+        space = PointsExchange::SPACE,
+        seeds = [b"points-exchange", from_brand.key().as_ref(), to_brand.key().as_ref()],
+        bump
+    )]
+    pub points_exchange: Account<'info, PointsExchange>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    #[account(mut)]
+    pub from_authority: Signer<'info>,
+    pub to_authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExchangePoints<'info> {
+    pub from_brand: Account<'info, Brand>,
+    pub to_brand: Account<'info, Brand>,
+    #[account(
+        mut,
+        seeds = [b"points-exchange", from_brand.key().as_ref(), to_brand.key().as_ref()],
+        bump
+    )]
+    pub points_exchange: Account<'info, PointsExchange>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"user-points", from_brand.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub from_user_points: Account<'info, UserPoints>,
+    /// Left out when `from_brand` never set a points policy
+    #[account(seeds = [b"points-policy", from_brand.key().as_ref()], bump)]
+    pub from_points_policy: Option<Account<'info, PointsPolicy>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserPoints::SPACE,
+        seeds = [b"user-points", to_brand.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub to_user_points: Account<'info, UserPoints>,
+    /// CHECK: `to_brand`'s leaderboard, updated once the brand has created it
+    #[account(mut, seeds = [b"leaderboard", to_brand.key().as_ref()], bump)]
+    pub to_leaderboard: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,312 @@
+use crate::errors::CepError;
+use crate::leaderboard::state::Leaderboard;
+use crate::season::state::SeasonCredit;
+use anchor_lang::prelude::*;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// How a brand's points lose their value over time.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PointExpiry {
+    #[default]
+    None,
+    /// Points expire in full `days` after the day they were earned
+    Fixed { days: u16 },
+    /// Points lose value linearly until nothing is left after `days`
+    LinearDecay { days: u16 },
+}
+
+impl PointExpiry {
+    /// Current value of the points in `bucket`.
+    pub fn value(&self, bucket: &PointsBucket, now: i64) -> u64 {
+        match *self {
+            PointExpiry::None => bucket.amount,
+            PointExpiry::Fixed { days } => {
+                if now < bucket.ends_at(days) {
+                    bucket.amount
+                } else {
+                    0
+                }
+            }
+            PointExpiry::LinearDecay { days } => {
+                let (left, window) = bucket.time_left(days, now);
+                (u128::from(bucket.amount) * left / window) as u64
+            }
+        }
+    }
+
+    /// Amount to take out of `bucket` so that its value drops by `value`.
+    fn backing(&self, bucket: &PointsBucket, value: u64, now: i64) -> u64 {
+        match *self {
+            PointExpiry::LinearDecay { days } if value < self.value(bucket, now) => {
+                let (left, window) = bucket.time_left(days, now);
+                let backing = (u128::from(value) * window).div_ceil(left);
+                backing.min(u128::from(bucket.amount)) as u64
+            }
+            PointExpiry::LinearDecay { .. } => bucket.amount,
+            _ => value,
+        }
+    }
+}
+
+/// A brand's points policy, applied whenever its points are read or spent.
+#[account]
+pub struct PointsPolicy {
+    pub brand: Pubkey,
+    pub expiry: PointExpiry,
+    pub updated_at: i64,
+}
+
+impl PointsPolicy {
+    pub const SPACE: usize = 8 // Discriminator
+        + 32 // Brand
+        + 1 + 2 // Expiry
+        + 8; // Updated at
+
+    /// Expiry under `policy`, where brands that never set one keep their
+    /// points forever.
+    pub fn expiry(policy: Option<&PointsPolicy>) -> PointExpiry {
+        policy.map_or(PointExpiry::None, |policy| policy.expiry)
+    }
+}
+
+/// Points earned on one UTC day.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct PointsBucket {
+    pub day: i64,
+    pub amount: u64,
+}
+
+impl PointsBucket {
+    pub const SPACE: usize = 8 + 8;
+
+    fn ends_at(&self, days: u16) -> i64 {
+        (self.day + i64::from(days)) * SECONDS_PER_DAY
+    }
+
+    /// Seconds left before the bucket is worth nothing, out of its window.
+    fn time_left(&self, days: u16, now: i64) -> (u128, u128) {
+        let window = i64::from(days) * SECONDS_PER_DAY;
+        let left = (self.ends_at(days) - now).clamp(0, window);
+        (left as u128, window as u128)
+    }
+}
+
+/// A user's points balance with one brand.
+#[account]
+#[derive(Default)]
+pub struct UserPoints {
+    pub brand: Pubkey,
+    pub user: Pubkey,
+    pub balance: u64, // Points held in `buckets`, before the brand's policy applies
+    pub lifetime_earned: u64, // Every point ever credited
+    pub buckets: Vec<PointsBucket>, // Oldest first
+    pub expired: u64, // Points dropped by expiry
+}
+
+impl UserPoints {
+    pub const MAX_BUCKETS: usize = 32;
+
+    pub const SPACE: usize = 8 // Discriminator
+        + 32 // Brand
+        + 32 // User
+        + 8 // Balance
+        + 8 // Lifetime earned
+        + 4 + PointsBucket::SPACE * Self::MAX_BUCKETS // Buckets
+        + 8; // Expired
+
+    /// Adds `amount` to today's bucket. Once every bucket is taken, the two
+    /// oldest are folded together under the older date, so no points ever
+    /// outlive the day they were earned and fresh points keep their own.
+    pub fn credit(&mut self, amount: u64, now: i64) {
+        self.balance = self.balance.saturating_add(amount);
+        self.lifetime_earned = self.lifetime_earned.saturating_add(amount);
+
+        let day = now.div_euclid(SECONDS_PER_DAY);
+        match self.buckets.last_mut() {
+            Some(bucket) if bucket.day == day => {
+                bucket.amount = bucket.amount.saturating_add(amount);
+            }
+            _ => self.buckets.push(PointsBucket { day, amount }),
+        }
+        if self.buckets.len() > Self::MAX_BUCKETS {
+            let second = self.buckets.remove(1);
+            let oldest = &mut self.buckets[0];
+            oldest.amount = oldest.amount.saturating_add(second.amount);
+        }
+    }
+
+    /// Points the user can spend right now.
+    pub fn available(&self, expiry: &PointExpiry, now: i64) -> u64 {
+        self.buckets.iter().fold(0u64, |total, bucket| {
+            total.saturating_add(expiry.value(bucket, now))
+        })
+    }
+
+    /// Spends `amount` of the available points, oldest first.
+    pub fn debit(&mut self, amount: u64, expiry: &PointExpiry, now: i64) -> Result<()> {
+        require!(
+            self.available(expiry, now) >= amount,
+            CepError::InsufficientPoints
+        );
+
+        let mut remaining = amount;
+        for bucket in self.buckets.iter_mut() {
+            if remaining == 0 {
+                break;
+            }
+            let taken = expiry.value(bucket, now).min(remaining);
+            if taken == 0 {
+                continue;
+            }
+            let backing = expiry.backing(bucket, taken, now);
+            bucket.amount -= backing;
+            self.balance = self
+                .balance
+                .checked_sub(backing)
+                .ok_or(CepError::PointsBalanceMismatch)?;
+            remaining -= taken;
+        }
+        self.buckets.retain(|bucket| bucket.amount > 0);
+        Ok(())
+    }
+
+    /// Drops the buckets that are worth nothing anymore and returns how
+    /// many points went with them.
+    pub fn expire(&mut self, expiry: &PointExpiry, now: i64) -> Result<u64> {
+        let mut expired = 0u64;
+        self.buckets.retain(|bucket| {
+            let worthless = expiry.value(bucket, now) == 0;
+            if worthless {
+                expired = expired.saturating_add(bucket.amount);
+            }
+            !worthless
+        });
+        self.balance = self
+            .balance
+            .checked_sub(expired)
+            .ok_or(CepError::PointsBalanceMismatch)?;
+        self.expired = self.expired.saturating_add(expired);
+        Ok(expired)
+    }
+}
+
+/// Lets users of `from_brand` convert their points into `to_brand` points.
+#[account]
+pub struct PointsExchange {
+    pub from_brand: Pubkey,
+    pub to_brand: Pubkey,
+    pub rate_bps: u32,    // `to_brand` points per 10,000 `from_brand` points
+    pub daily_cap: u64,   // `from_brand` points exchangeable per UTC day
+    pub current_day: i64, // Day `exchanged_today` counts for
+    pub exchanged_today: u64,
+    pub created_at: i64,
+}
+
+impl PointsExchange {
+    pub const SPACE: usize = 8 // Discriminator
+        + 32 // From brand
+        + 32 // To brand
+        + 4 // Rate
+        + 8 // Daily cap
+        + 8 // Current day
+        + 8 // Exchanged today
+        + 8; // Created at
+
+    /// Takes `amount` out of today's cap and returns the points it buys.
+    pub fn exchange(&mut self, amount: u64, now: i64) -> Result<u64> {
+        let today = now.div_euclid(SECONDS_PER_DAY);
+        if today != self.current_day {
+            self.current_day = today;
+            self.exchanged_today = 0;
+        }
+        self.exchanged_today = self
+            .exchanged_today
+            .checked_add(amount)
+            .filter(|exchanged| *exchanged <= self.daily_cap)
+            .ok_or(CepError::ExchangeCapExceeded)?;
+
+        let credited = u128::from(amount) * u128::from(self.rate_bps) / 10_000;
+        require!(credited > 0, CepError::ExchangeAmountTooSmall);
+        u64::try_from(credited).map_err(|_| CepError::ExchangeCapExceeded.into())
+    }
+}
+
+/// A user's total in a ranking.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct Standing {
+    pub user: Pubkey,
+    pub points: u64,
+}
+
+impl Standing {
+    pub const SPACE: usize = 32 + 8;
+
+    /// Records `user`'s new total in `standings`, which are kept sorted by
+    /// points and capped at `capacity`. Ties keep the earlier entry ahead.
+    pub fn update(standings: &mut Vec<Standing>, user: Pubkey, points: u64, capacity: usize) {
+        standings.retain(|standing| standing.user != user);
+        let rank = standings
+            .iter()
+            .position(|standing| standing.points < points)
+            .unwrap_or(standings.len());
+        if rank < capacity {
+            standings.insert(rank, Standing { user, points });
+            standings.truncate(capacity);
+        }
+    }
+}
+
+/// Credits an award's points to the user's brand balance, their rank on
+/// the brand's leaderboard and, when the award comes with a season, to
+/// their season total.
+pub fn credit_award_points(
+    brand: Pubkey,
+    user: Pubkey,
+    points: u64,
+    now: i64,
+    user_points: &mut UserPoints,
+    leaderboard: &AccountInfo,
+    season: Option<SeasonCredit>,
+) -> Result<()> {
+    user_points.brand = brand;
+    user_points.user = user;
+    user_points.credit(points, now);
+    Leaderboard::record_if_created(leaderboard, user, user_points.lifetime_earned, now)?;
+
+    match season {
+        Some(season) => season.credit(&brand, user, points, now),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn credit_folds_the_oldest_buckets_once_full() {
+        let mut points = UserPoints::default();
+        for day in 0..=UserPoints::MAX_BUCKETS as i64 {
+            points.credit(10 + day as u64, day * SECONDS_PER_DAY);
+        }
+
+        assert_eq!(points.buckets.len(), UserPoints::MAX_BUCKETS);
+        assert!(points.buckets[0] == PointsBucket { day: 0, amount: 21 });
+        assert!(points.buckets[1] == PointsBucket { day: 2, amount: 12 });
+        let newest = UserPoints::MAX_BUCKETS as i64;
+        assert!(
+            points.buckets.last()
+                == Some(&PointsBucket {
+                    day: newest,
+                    amount: 10 + newest as u64
+                })
+        );
+
+        // Today's points last their full window instead of the oldest bucket's
+        let expiry = PointExpiry::Fixed { days: 30 };
+        let now = (newest + 29) * SECONDS_PER_DAY;
+        assert_eq!(points.available(&expiry, now), 10 + newest as u64);
+        assert_eq!(points.balance, points.lifetime_earned);
+    }
+}
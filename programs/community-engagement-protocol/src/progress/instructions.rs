@@ -0,0 +1,205 @@
+use super::state::*;
+use crate::achievement::instructions::{
+    create_badge_account_if_missing, create_user_achievement, mint_badge,
+};
+use crate::achievement::state::{
+    Achievement, UserAchievement, UserAchievements, MINT_AUTHORITY_SEED,
+};
+use crate::attester::state::{authorize_award, Attester};
+use crate::brand::state::Brand;
+use crate::errors::CepError;
+use crate::points::state::{credit_award_points, UserPoints};
+use crate::season::state::{Season, SeasonCredit};
+use crate::ProgramState;
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+    token_interface::{Mint, TokenInterface},
+};
+
+/// Adds `delta` to one of the user's counters for `achievement` and awards
+/// the achievement's badge as soon as all of its threshold rules hold. The
+/// user's prerequisite records go in `remaining_accounts`; without them, or
+/// while the achievement isn't available, the progress is still recorded
+/// but the award waits.
+pub fn record_progress(ctx: Context<RecordProgress>, metric: String, delta: u64) -> Result<()> {
+    let achievement = &mut ctx.accounts.achievement;
+    let clock = Clock::get()?;
+
+    require!(!achievement.archived, CepError::AchievementArchived);
+
+    let progress = &mut ctx.accounts.user_progress;
+    progress.user = ctx.accounts.user.key();
+    progress.achievement = achievement.key();
+    progress.updated_at = clock.unix_timestamp;
+    let value = progress.record(&achievement.rules, &metric, delta)?;
+
+    // The record only exists once the badge has been awarded
+    let user_achievement = &ctx.accounts.user_achievement;
+    let reached = user_achievement.data_is_empty()
+        && progress.meets(&achievement.rules)
+        && achievement.has_prerequisites(&ctx.accounts.user.key(), ctx.remaining_accounts)
+        && achievement.record_awards(clock.unix_timestamp, 1).is_ok();
+
+    // Attesters need a grant for the achievement to record progress, and
+    // spend quota only when it leads to an award
+    authorize_award(
+        &ctx.accounts.brand,
+        &achievement.key(),
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.program_state.tronic_admin,
+        ctx.accounts.attester.as_mut(),
+        u32::from(reached),
+    )?;
+
+    msg!(
+        "Progress '{}' for user {} is now {}",
+        metric,
+        ctx.accounts.user.key(),
+        value
+    );
+
+    if !reached {
+        return Ok(());
+    }
+
+    let achievement_key = achievement.key();
+    create_user_achievement(
+        user_achievement,
+        ctx.bumps.user_achievement,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+        &UserAchievement {
+            user: ctx.accounts.user.key(),
+            achievement: achievement_key,
+            brand: achievement.brand,
+            awarded_at: clock.unix_timestamp,
+        },
+    )?;
+    ctx.accounts
+        .user_achievements
+        .achievements
+        .push(achievement_key);
+
+    let user_token_account = &ctx.accounts.user_token_account.to_account_info();
+    require_keys_eq!(
+        user_token_account.key(),
+        get_associated_token_address_with_program_id(
+            ctx.accounts.user.key,
+            &ctx.accounts.token_mint.key(),
+            ctx.accounts.token_program.key,
+        ),
+        ErrorCode::ConstraintAddress
+    );
+    create_badge_account_if_missing(
+        user_token_account,
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.token_mint,
+        &ctx.accounts.authority,
+        &ctx.accounts.token_program,
+        &ctx.accounts.associated_token_program,
+        &ctx.accounts.system_program,
+    )?;
+    mint_badge(
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_mint,
+        user_token_account,
+        &ctx.accounts.mint_authority,
+        &[
+            MINT_AUTHORITY_SEED,
+            ctx.accounts.brand.key().as_ref(),
+            &[ctx.bumps.mint_authority],
+        ],
+        &ctx.accounts.authority,
+        achievement.soulbound,
+    )?;
+
+    credit_award_points(
+        achievement.brand,
+        ctx.accounts.user.key(),
+        achievement.points.into(),
+        clock.unix_timestamp,
+        &mut ctx.accounts.user_points,
+        &ctx.accounts.leaderboard,
+        SeasonCredit::new(
+            ctx.accounts.season.as_mut(),
+            ctx.accounts.user_season_points.as_deref(),
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?,
+    )?;
+
+    msg!(
+        "Achievement '{}' unlocked by user {}",
+        achievement.name,
+        ctx.accounts.user.key()
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RecordProgress<'info> {
+    pub brand: Account<'info, Brand>,
+    #[account(
+        mut,
+        constraint = achievement.brand == brand.key(),
+        constraint = achievement.token_mint == Some(token_mint.key()) @ CepError::InvalidAchievementType
+    )]
+    pub achievement: Account<'info, Achievement>,
+    /// CHECK: This account is used to store the public key of the user making progress
+    pub user: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = UserProgress::SPACE,
+        seeds = [b"user-progress", achievement.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_progress: Account<'info, UserProgress>,
+    #[account(mut, has_one = user)]
+    pub user_achievements: Account<'info, UserAchievements>,
+    /// CHECK: Award record, created here once the rules are met
+    #[account(
+        mut,
+        seeds = [b"user-achievement", achievement.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_achievement: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = UserPoints::SPACE,
+        seeds = [b"user-points", brand.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_points: Account<'info, UserPoints>,
+    /// CHECK: The brand's leaderboard, ranked by lifetime points, updated
+    /// once the brand has created it
+    #[account(mut, seeds = [b"leaderboard", brand.key().as_ref()], bump)]
+    pub leaderboard: UncheckedAccount<'info>,
+    /// Season the award's points also count towards
+    #[account(mut, has_one = brand)]
+    pub season: Option<Account<'info, Season>>,
+    /// CHECK: The user's points PDA for `season`, created on first credit
+    #[account(mut)]
+    pub user_season_points: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// Required when `authority` records progress as a delegated attester
+    #[account(mut)]
+    pub attester: Option<Account<'info, Attester>>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    #[account(mut)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: The brand's PDA that mints and freezes badges
+    #[account(seeds = [MINT_AUTHORITY_SEED, brand.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    /// CHECK: The user's associated token account, created on award and
+    /// checked against its address in the handler
+    #[account(mut)]
+    pub user_token_account: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
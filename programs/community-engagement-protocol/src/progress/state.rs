@@ -0,0 +1,62 @@
+use crate::achievement::state::{Achievement, ThresholdRule};
+use crate::errors::CepError;
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct ProgressCounter {
+    pub metric: String,
+    pub value: u64,
+}
+
+#[account]
+pub struct UserProgress {
+    pub user: Pubkey,
+    pub achievement: Pubkey,
+    pub counters: Vec<ProgressCounter>, // One per metric the achievement's rules use
+    pub updated_at: i64,
+}
+
+impl UserProgress {
+    pub const SPACE: usize = 8 // Discriminator
+        + 32 // User
+        + 32 // Achievement
+        + 4 + ThresholdRule::SPACE * Achievement::MAX_RULES // Counters
+        + 8; // Updated at
+
+    /// Adds `delta` to `metric`, which must be one of the `rules` metrics.
+    pub fn record(&mut self, rules: &[ThresholdRule], metric: &str, delta: u64) -> Result<u64> {
+        require!(
+            rules.iter().any(|rule| rule.metric == metric),
+            CepError::UnknownMetric
+        );
+
+        let index = match self.counters.iter().position(|c| c.metric == metric) {
+            Some(index) => index,
+            None => {
+                self.counters.push(ProgressCounter {
+                    metric: metric.to_string(),
+                    value: 0,
+                });
+                self.counters.len() - 1
+            }
+        };
+        let counter = &mut self.counters[index];
+        counter.value = counter.value.saturating_add(delta);
+        Ok(counter.value)
+    }
+
+    pub fn value(&self, metric: &str) -> u64 {
+        self.counters
+            .iter()
+            .find(|c| c.metric == metric)
+            .map_or(0, |c| c.value)
+    }
+
+    /// Whether every rule's threshold has been reached.
+    pub fn meets(&self, rules: &[ThresholdRule]) -> bool {
+        !rules.is_empty()
+            && rules
+                .iter()
+                .all(|rule| self.value(&rule.metric) >= rule.threshold)
+    }
+}
@@ -0,0 +1,221 @@
+use super::state::*;
+use crate::achievement::state::Achievement;
+use crate::brand::state::Brand;
+use crate::errors::CepError;
+use crate::points::state::{credit_award_points, UserPoints};
+use crate::reward::instructions::mint_reward_tokens;
+use crate::reward::state::{Reward, RewardType};
+use crate::season::state::{Season, SeasonCredit};
+use crate::ProgramState;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token};
+
+/// Creates a quest over `achievements`, whose accounts follow in
+/// `remaining_accounts` in the same order so each step can be checked to
+/// belong to the brand.
+pub fn create_quest(
+    ctx: Context<CreateQuest>,
+    name: String,
+    achievements: Vec<Pubkey>,
+    ordered: bool,
+    deadline: Option<i64>,
+    reward_amount: u64,
+    bonus_points: u64,
+) -> Result<()> {
+    let quest = &mut ctx.accounts.quest;
+    let clock = Clock::get()?;
+
+    if name.chars().count() > 50 {
+        return Err(CepError::NameTooLong.into());
+    }
+    require!(
+        !achievements.is_empty() && achievements.len() <= Quest::MAX_ACHIEVEMENTS,
+        CepError::InvalidQuestSteps
+    );
+    require!(
+        ctx.remaining_accounts.len() == achievements.len(),
+        CepError::InvalidQuestSteps
+    );
+    for (step, info) in achievements.iter().zip(ctx.remaining_accounts) {
+        require_keys_eq!(info.key(), *step, CepError::InvalidQuestSteps);
+        require_keys_eq!(*info.owner, crate::ID, CepError::InvalidQuestSteps);
+        let achievement = Achievement::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require_keys_eq!(
+            achievement.brand,
+            ctx.accounts.brand.key(),
+            CepError::InvalidQuestSteps
+        );
+    }
+
+    if let Some(reward) = &ctx.accounts.reward {
+        require!(
+            matches!(reward.reward_type, RewardType::Fungible { .. }),
+            CepError::InvalidRewardType
+        );
+        require!(reward_amount > 0, CepError::InvalidRewardType);
+    }
+
+    quest.brand = ctx.accounts.brand.key();
+    quest.name = name;
+    quest.achievements = achievements;
+    quest.ordered = ordered;
+    quest.deadline = deadline;
+    quest.reward = ctx.accounts.reward.as_ref().map(|reward| reward.key());
+    quest.reward_amount = reward_amount;
+    quest.bonus_points = bonus_points;
+    quest.created_at = clock.unix_timestamp;
+    quest.completed_count = 0;
+
+    msg!(
+        "Quest '{}' created with {} steps",
+        quest.name,
+        quest.achievements.len()
+    );
+    Ok(())
+}
+
+/// Verifies the user's `UserAchievement` records for every step, passed in
+/// `remaining_accounts`, then credits the bonus points and issues the linked
+/// reward.
+pub fn complete_quest(ctx: Context<CompleteQuest>) -> Result<()> {
+    let quest = &mut ctx.accounts.quest;
+    let user = ctx.accounts.user.key();
+    let clock = Clock::get()?;
+
+    if let Some(deadline) = quest.deadline {
+        require!(clock.unix_timestamp <= deadline, CepError::QuestExpired);
+    }
+    quest.verify_steps(&user, ctx.remaining_accounts)?;
+
+    let user_quest = &mut ctx.accounts.user_quest;
+    user_quest.quest = quest.key();
+    user_quest.user = user;
+    user_quest.completed_at = clock.unix_timestamp;
+    quest.completed_count += 1;
+
+    credit_award_points(
+        quest.brand,
+        user,
+        quest.bonus_points,
+        clock.unix_timestamp,
+        &mut ctx.accounts.user_points,
+        &ctx.accounts.leaderboard,
+        SeasonCredit::new(
+            ctx.accounts.season.as_mut(),
+            ctx.accounts.user_season_points.as_deref(),
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?,
+    )?;
+
+    if let Some(reward_key) = quest.reward {
+        let (Some(reward), Some(token_mint), Some(user_token_account)) = (
+            ctx.accounts.reward.as_mut(),
+            &ctx.accounts.token_mint,
+            &ctx.accounts.user_token_account,
+        ) else {
+            return Err(CepError::RewardAccountsRequired.into());
+        };
+        require_keys_eq!(reward.key(), reward_key, CepError::InvalidRewardType);
+        mint_reward_tokens(
+            reward,
+            token_mint,
+            &ctx.accounts.user,
+            user_token_account,
+            &ctx.accounts.authority,
+            &ctx.accounts.token_program,
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.system_program,
+            quest.reward_amount,
+        )?;
+    }
+
+    msg!("Quest '{}' completed by user {}", quest.name, user);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateQuest<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 // Discriminator
+            + 32 // Brand
+            + 4 + 50 // Name
+            + 4 + 32 * Quest::MAX_ACHIEVEMENTS // Achievements
+            + 1 // Ordered
+            + 1 + 8 // Deadline
+            + 1 + 32 // Reward
+            + 8 // Reward amount
+            + 8 // Bonus points
+            + 8 // Created at
+            + 8 // Completed count
+    )]
+    pub quest: Account<'info, Quest>,
+    /// Fungible reward issued on completion
+    #[account(has_one = brand)]
+    pub reward: Option<Account<'info, Reward>>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CompleteQuest<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(mut, has_one = brand)]
+    pub quest: Account<'info, Quest>,
+    /// CHECK: This account is used to store the public key of the user completing the quest
+    pub user: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 8,
+        seeds = [b"user-quest", quest.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_quest: Account<'info, UserQuest>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = UserPoints::SPACE,
+        seeds = [b"user-points", brand.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_points: Account<'info, UserPoints>,
+    /// CHECK: The brand's leaderboard, ranked by lifetime points, updated
+    /// once the brand has created it
+    #[account(mut, seeds = [b"leaderboard", brand.key().as_ref()], bump)]
+    pub leaderboard: UncheckedAccount<'info>,
+    /// Season the bonus points also count towards
+    #[account(mut, has_one = brand)]
+    pub season: Option<Account<'info, Season>>,
+    /// CHECK: The user's points PDA for `season`, created on first credit
+    #[account(mut)]
+    pub user_season_points: Option<UncheckedAccount<'info>>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// The quest's reward, required when it has one
+    #[account(mut)]
+    pub reward: Option<Account<'info, Reward>>,
+    #[account(mut)]
+    pub token_mint: Option<Account<'info, Mint>>,
+    /// CHECK: The user's associated token account for the reward, created if missing
+    #[account(mut)]
+    pub user_token_account: Option<UncheckedAccount<'info>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
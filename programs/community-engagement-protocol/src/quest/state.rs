@@ -0,0 +1,46 @@
+use crate::achievement::state::UserAchievement;
+use crate::errors::CepError;
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Quest {
+    pub brand: Pubkey,
+    pub name: String,
+    pub achievements: Vec<Pubkey>, // Steps of the quest
+    pub ordered: bool,             // Steps must be awarded in the listed order
+    pub deadline: Option<i64>,     // Quest can't be completed after this time
+    pub reward: Option<Pubkey>,    // Fungible reward issued on completion
+    pub reward_amount: u64,        // Tokens of `reward` issued per completion
+    pub bonus_points: u64,         // Points credited per completion
+    pub created_at: i64,
+    pub completed_count: u64, // Number of users who completed the quest
+}
+
+impl Quest {
+    pub const MAX_ACHIEVEMENTS: usize = 10;
+
+    /// Checks that `records` hold the user's award for every step and, for
+    /// ordered quests, that the steps were awarded in order.
+    pub fn verify_steps(&self, user: &Pubkey, records: &[AccountInfo]) -> Result<()> {
+        let mut previous_award = i64::MIN;
+        for achievement in &self.achievements {
+            let record = UserAchievement::find(records, user, achievement)
+                .ok_or(CepError::QuestIncomplete)?;
+            if self.ordered {
+                require!(
+                    record.awarded_at >= previous_award,
+                    CepError::QuestStepsOutOfOrder
+                );
+                previous_award = record.awarded_at;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[account]
+pub struct UserQuest {
+    pub quest: Pubkey,
+    pub user: Pubkey,
+    pub completed_at: i64,
+}
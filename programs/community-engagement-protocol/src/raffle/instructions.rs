@@ -0,0 +1,308 @@
+use super::state::*;
+use crate::achievement::state::UserAchievement;
+use crate::brand::state::Brand;
+use crate::errors::CepError;
+use crate::membership::state::{MembershipTier, MembershipToken};
+use crate::points::state::UserPoints;
+use crate::reward::instructions::mint_reward_tokens;
+use crate::reward::state::{Reward, RewardType};
+use crate::ProgramState;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::slot_hashes::MAX_ENTRIES;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token};
+use anchor_spl::token_interface::TokenAccount;
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_raffle(
+    ctx: Context<CreateRaffle>,
+    name: String,
+    requirement: EntryRequirement,
+    max_entries: u16,
+    prize_amount: u64,
+    winner_count: u8,
+    closes_at: i64,
+    oracle: Option<Pubkey>,
+) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    let clock = Clock::get()?;
+
+    if name.chars().count() > 50 {
+        return Err(CepError::NameTooLong.into());
+    }
+    require!(
+        (1..=Raffle::MAX_ENTRIES).contains(&usize::from(max_entries))
+            && (1..=Raffle::MAX_WINNERS).contains(&usize::from(winner_count))
+            && closes_at > clock.unix_timestamp,
+        CepError::InvalidRaffle
+    );
+    require!(
+        matches!(ctx.accounts.prize.reward_type, RewardType::Fungible { .. }),
+        CepError::InvalidRewardType
+    );
+    if let EntryRequirement::Membership {
+        membership,
+        tier_id,
+    } = &requirement
+    {
+        require!(
+            ctx.accounts.brand.memberships.contains(membership)
+                && tier_id
+                    .iter()
+                    .all(|tier_id| tier_id.len() <= MembershipTier::MAX_ID_LENGTH),
+            CepError::InvalidRaffle
+        );
+    }
+
+    raffle.brand = ctx.accounts.brand.key();
+    raffle.name = name;
+    raffle.requirement = requirement;
+    raffle.max_entries = max_entries;
+    raffle.prize = ctx.accounts.prize.key();
+    raffle.prize_amount = prize_amount;
+    raffle.winner_count = winner_count;
+    raffle.closes_at = closes_at;
+    raffle.oracle = oracle;
+    raffle.draw_slot = None;
+    raffle.entries = vec![];
+    raffle.winners = vec![];
+    raffle.drawn_at = None;
+    raffle.created_at = clock.unix_timestamp;
+
+    msg!(
+        "Raffle '{}' created with {} winners, closing at {}",
+        raffle.name,
+        winner_count,
+        closes_at
+    );
+    Ok(())
+}
+
+/// Enters the signing user into the raffle. The award record, points
+/// account or membership token the entry requirement asks for has to be
+/// passed.
+pub fn enter_raffle(ctx: Context<EnterRaffle>) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    let user = ctx.accounts.user.key();
+
+    require!(
+        Clock::get()?.unix_timestamp < raffle.closes_at,
+        CepError::RaffleClosed
+    );
+    require!(
+        raffle.entries.len() < usize::from(raffle.max_entries),
+        CepError::RaffleFull
+    );
+    require!(
+        !raffle.entries.contains(&user),
+        CepError::AlreadyEnteredRaffle
+    );
+    raffle.requirement.check(
+        &raffle.brand,
+        &user,
+        ctx.accounts.user_achievement.as_deref(),
+        ctx.accounts.user_points.as_deref(),
+        ctx.accounts.membership_token.as_deref(),
+        ctx.accounts.member_token_account.as_deref(),
+    )?;
+
+    raffle.entries.push(user);
+
+    msg!("User {} entered raffle '{}'", user, raffle.name);
+    Ok(())
+}
+
+/// Picks the slot whose hash draws a raffle without an oracle, a few slots
+/// after the raffle has closed. Anyone can call this, and call it again once
+/// the scheduled slot's hash has aged out of `SlotHashes` undrawn.
+pub fn schedule_raffle_draw(ctx: Context<ScheduleRaffleDraw>) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp >= raffle.closes_at,
+        CepError::RaffleNotClosed
+    );
+    require!(raffle.oracle.is_none(), CepError::InvalidRandomness);
+    require!(
+        raffle
+            .draw_slot
+            .iter()
+            .all(|slot| clock.slot > slot + MAX_ENTRIES as u64),
+        CepError::RaffleDrawAlreadyScheduled
+    );
+
+    let draw_slot = clock.slot + Raffle::DRAW_DELAY_SLOTS;
+    raffle.draw_slot = Some(draw_slot);
+
+    msg!("Raffle '{}' draws at slot {}", raffle.name, draw_slot);
+    Ok(())
+}
+
+/// Draws the winners once the raffle has closed. Randomness comes from the
+/// raffle's VRF oracle when it has one, otherwise from the hash of its
+/// scheduled draw slot, which has to be drawn before it ages out of the
+/// `SlotHashes` sysvar. Anyone can call this.
+pub fn draw_raffle(ctx: Context<DrawRaffle>) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp >= raffle.closes_at,
+        CepError::RaffleNotClosed
+    );
+    require!(raffle.drawn_at.is_none(), CepError::RaffleAlreadyDrawn);
+
+    let randomness = match raffle.oracle {
+        Some(oracle) => {
+            let randomness = ctx
+                .accounts
+                .randomness
+                .as_ref()
+                .ok_or(CepError::InvalidRandomness)?;
+            OracleRandomness::read(randomness, &oracle, &raffle.key())?
+        }
+        None => {
+            let draw_slot = raffle.draw_slot.ok_or(CepError::RaffleDrawNotReady)?;
+            slot_hash(&ctx.accounts.slot_hashes, draw_slot)?
+        }
+    };
+    let raffle_key = raffle.key();
+    raffle.draw(&raffle_key, &randomness, clock.unix_timestamp);
+
+    msg!(
+        "Raffle '{}' drew {} winners from {} entries",
+        raffle.name,
+        raffle.winners.len(),
+        raffle.entries.len()
+    );
+    Ok(())
+}
+
+/// Issues the prize to one of the raffle's winners. The prize's mint
+/// authority has to sign as `authority`.
+pub fn issue_raffle_prize(ctx: Context<IssueRafflePrize>) -> Result<()> {
+    let raffle = &mut ctx.accounts.raffle;
+    let winner_key = ctx.accounts.winner.key();
+
+    let winner = raffle
+        .winners
+        .iter_mut()
+        .find(|winner| winner.user == winner_key)
+        .ok_or(CepError::NotRaffleWinner)?;
+    require!(!winner.issued, CepError::RafflePrizeIssued);
+    winner.issued = true;
+
+    mint_reward_tokens(
+        &mut ctx.accounts.prize,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.winner,
+        &ctx.accounts.winner_token_account,
+        &ctx.accounts.authority,
+        &ctx.accounts.token_program,
+        &ctx.accounts.associated_token_program,
+        &ctx.accounts.system_program,
+        raffle.prize_amount,
+    )?;
+
+    msg!(
+        "Prize of raffle '{}' issued to user {}",
+        raffle.name,
+        winner_key
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateRaffle<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 // Discriminator
+            + 32 // Brand
+            + 4 + 50 // Name
+            + 1 + 32 + 1 + 4 + MembershipTier::MAX_ID_LENGTH // Requirement
+            + 2 // Max entries
+            + 32 // Prize
+            + 8 // Prize amount
+            + 1 // Winner count
+            + 8 // Closes at
+            + 1 + 32 // Oracle
+            + 1 + 8 // Draw slot
+            + 4 + 32 * Raffle::MAX_ENTRIES // Entries
+            + 4 + (32 + 1) * Raffle::MAX_WINNERS // Winners
+            + 1 + 8 // Drawn at
+            + 8 // Created at
+    )]
+    pub raffle: Account<'info, Raffle>,
+    #[account(has_one = brand)]
+    pub prize: Account<'info, Reward>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EnterRaffle<'info> {
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+    pub user: Signer<'info>,
+    /// Required for raffles open to holders of an achievement
+    pub user_achievement: Option<Account<'info, UserAchievement>>,
+    /// Required for raffles with a points threshold
+    pub user_points: Option<Account<'info, UserPoints>>,
+    /// Required for raffles open to members, with the user's token account
+    /// holding the membership token
+    pub membership_token: Option<Account<'info, MembershipToken>>,
+    pub member_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct ScheduleRaffleDraw<'info> {
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+}
+
+#[derive(Accounts)]
+pub struct DrawRaffle<'info> {
+    #[account(mut)]
+    pub raffle: Account<'info, Raffle>,
+    /// CHECK: The SlotHashes sysvar, read directly
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+    /// CHECK: Randomness from the raffle's oracle program, checked in the instruction
+    pub randomness: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct IssueRafflePrize<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(mut, has_one = brand, has_one = prize)]
+    pub raffle: Account<'info, Raffle>,
+    #[account(mut)]
+    pub prize: Account<'info, Reward>,
+    #[account(mut)]
+    pub token_mint: Account<'info, Mint>,
+    /// CHECK: The winner receiving the prize, checked against the raffle
+    pub winner: UncheckedAccount<'info>,
+    /// CHECK: The winner's associated token account, created if missing
+    #[account(mut)]
+    pub winner_token_account: UncheckedAccount<'info>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
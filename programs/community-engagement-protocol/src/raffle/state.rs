@@ -0,0 +1,155 @@
+use crate::achievement::state::UserAchievement;
+use crate::errors::CepError;
+use crate::membership::state::MembershipToken;
+use crate::points::state::UserPoints;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_spl::token_interface::TokenAccount;
+use std::cmp::Ordering;
+
+/// What a user needs to enter a raffle.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum EntryRequirement {
+    None,
+    /// Holds the award of `achievement`
+    Achievement {
+        achievement: Pubkey,
+    },
+    /// Has earned at least `points` with the brand
+    MinPoints {
+        points: u64,
+    },
+    /// Holds a token of `membership`, from `tier_id` when one is given
+    Membership {
+        membership: Pubkey,
+        tier_id: Option<String>,
+    },
+}
+
+impl EntryRequirement {
+    pub fn check(
+        &self,
+        brand: &Pubkey,
+        user: &Pubkey,
+        user_achievement: Option<&UserAchievement>,
+        user_points: Option<&UserPoints>,
+        membership_token: Option<&MembershipToken>,
+        member_token_account: Option<&TokenAccount>,
+    ) -> Result<()> {
+        let met = match self {
+            EntryRequirement::None => true,
+            EntryRequirement::Achievement { achievement } => user_achievement
+                .is_some_and(|held| held.user == *user && held.achievement == *achievement),
+            EntryRequirement::MinPoints { points } => user_points.is_some_and(|earned| {
+                earned.brand == *brand && earned.user == *user && earned.lifetime_earned >= *points
+            }),
+            EntryRequirement::Membership {
+                membership,
+                tier_id,
+            } => match (membership_token, member_token_account) {
+                (Some(token), Some(account)) => {
+                    token.membership_data == *membership
+                        && tier_id.iter().all(|tier_id| token.tier_id == *tier_id)
+                        && account.mint == token.mint
+                        && account.owner == *user
+                        && account.amount > 0
+                }
+                _ => false,
+            },
+        };
+        require!(met, CepError::RaffleRequirementNotMet);
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct RaffleWinner {
+    pub user: Pubkey,
+    pub issued: bool, // Prize has been issued
+}
+
+#[account]
+pub struct Raffle {
+    pub brand: Pubkey,
+    pub name: String,
+    pub requirement: EntryRequirement,
+    pub max_entries: u16,
+    pub prize: Pubkey,     // Fungible reward issued to the winners
+    pub prize_amount: u64, // Tokens per winner
+    pub winner_count: u8,
+    pub closes_at: i64,         // Entries close and the draw opens at this time
+    pub oracle: Option<Pubkey>, // VRF program drawing instead of slot hashes
+    pub draw_slot: Option<u64>, // Slot whose hash draws a raffle without an oracle
+    pub entries: Vec<Pubkey>,
+    pub winners: Vec<RaffleWinner>,
+    pub drawn_at: Option<i64>,
+    pub created_at: i64,
+}
+
+impl Raffle {
+    pub const MAX_ENTRIES: usize = 200;
+    pub const MAX_WINNERS: usize = 10;
+    /// Slots between scheduling a draw and its draw slot, so the hash it
+    /// draws with is unknown when the slot is picked, past the current
+    /// leader's slots.
+    pub const DRAW_DELAY_SLOTS: u64 = 8;
+
+    /// Picks up to `winner_count` distinct entries from `randomness`.
+    pub fn draw(&mut self, raffle: &Pubkey, randomness: &[u8; 32], now: i64) {
+        let mut candidates = self.entries.clone();
+        let mut seed = hashv(&[randomness, raffle.as_ref()]).to_bytes();
+        while self.winners.len() < usize::from(self.winner_count) && !candidates.is_empty() {
+            let roll = u64::from_le_bytes(seed[..8].try_into().unwrap());
+            let index = (roll % candidates.len() as u64) as usize;
+            self.winners.push(RaffleWinner {
+                user: candidates.swap_remove(index),
+                issued: false,
+            });
+            seed = hashv(&[&seed]).to_bytes();
+        }
+        self.drawn_at = Some(now);
+    }
+}
+
+/// Randomness a VRF oracle wrote for a raffle. Oracle accounts are owned by
+/// the raffle's oracle program and hold an 8-byte header, the raffle they
+/// answer and 32 random bytes.
+pub struct OracleRandomness;
+
+impl OracleRandomness {
+    pub const LEN: usize = 8 + 32 + 32;
+
+    pub fn read(info: &AccountInfo, oracle: &Pubkey, raffle: &Pubkey) -> Result<[u8; 32]> {
+        require_keys_eq!(*info.owner, *oracle, CepError::InvalidRandomness);
+        let data = info.try_borrow_data()?;
+        require!(data.len() >= Self::LEN, CepError::InvalidRandomness);
+        require!(&data[8..40] == raffle.as_ref(), CepError::InvalidRandomness);
+        Ok(data[40..72].try_into().unwrap())
+    }
+}
+
+/// Reads the hash of the first block at or after `slot` from the
+/// `SlotHashes` sysvar, which is too large to deserialize whole. The sysvar
+/// only keeps the most recent 512 slots, so older slots can't be read.
+pub fn slot_hash(slot_hashes: &AccountInfo, slot: u64) -> Result<[u8; 32]> {
+    let data = slot_hashes.try_borrow_data()?;
+    // u64 entry count, then (slot, hash) pairs with the newest first
+    require!(data.len() >= 8, CepError::InvalidRandomness);
+    let count = u64::from_le_bytes(data[..8].try_into().unwrap()) as usize;
+
+    let mut newer = None;
+    for entry in data[8..].chunks_exact(8 + 32).take(count) {
+        let entry_slot = u64::from_le_bytes(entry[..8].try_into().unwrap());
+        let hash: [u8; 32] = entry[8..].try_into().unwrap();
+        match entry_slot.cmp(&slot) {
+            Ordering::Greater => newer = Some(hash),
+            Ordering::Equal => return Ok(hash),
+            Ordering::Less => return newer.ok_or(CepError::RaffleDrawNotReady.into()),
+        }
+    }
+    // Every slot left in the sysvar is past `slot`, whose block aged out
+    match newer {
+        Some(_) => Err(CepError::DrawSlotHashExpired.into()),
+        None => Err(CepError::RaffleDrawNotReady.into()),
+    }
+}
@@ -0,0 +1,206 @@
+use super::state::*;
+use crate::attester::state::{authorize_activity, Attester};
+use crate::brand::state::Brand;
+use crate::errors::CepError;
+use crate::points::state::{credit_award_points, UserPoints};
+use crate::reward::instructions::mint_reward_tokens;
+use crate::reward::state::Reward;
+use crate::ProgramState;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token};
+
+/// Sets or changes what the brand's referrals pay. Lowering `max_referrals`
+/// below what a referrer has already been credited for keeps those credits
+/// and stops crediting them any further.
+pub fn configure_referrals(
+    ctx: Context<ConfigureReferrals>,
+    points: u64,
+    reward_amount: u64,
+    max_referrals: u32,
+) -> Result<()> {
+    let referral_program = &mut ctx.accounts.referral_program;
+    referral_program.brand = ctx.accounts.brand.key();
+    referral_program.points = points;
+    referral_program.reward = ctx.accounts.reward.as_ref().map(|reward| reward.key());
+    referral_program.reward_amount = reward_amount;
+    referral_program.max_referrals = max_referrals;
+    referral_program.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Referrals for Brand '{}' pay {} points, up to {} per referrer",
+        ctx.accounts.brand.name,
+        points,
+        max_referrals
+    );
+    Ok(())
+}
+
+pub fn register_referral_code(ctx: Context<RegisterReferralCode>, code: String) -> Result<()> {
+    require!(
+        !code.is_empty() && code.len() <= ReferralCode::MAX_CODE_LENGTH,
+        CepError::InvalidReferralCode
+    );
+
+    let referral_code = &mut ctx.accounts.referral_code;
+    referral_code.brand = ctx.accounts.brand.key();
+    referral_code.referrer = ctx.accounts.referrer.key();
+    referral_code.code = code;
+    referral_code.referred_count = 0;
+    referral_code.credited_count = 0;
+    referral_code.created_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Referral code '{}' registered for user {}",
+        referral_code.code,
+        referral_code.referrer
+    );
+    Ok(())
+}
+
+/// Credits the referrer once the referred member completes a qualifying
+/// action, as judged by a brand authority or attester. Each referral is
+/// credited once, and each referrer up to the program's cap. The reward's
+/// mint authority has to sign as `authority` when the program has one.
+pub fn credit_referral(ctx: Context<CreditReferral>) -> Result<()> {
+    authorize_activity(
+        &ctx.accounts.brand,
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.program_state.tronic_admin,
+        ctx.accounts.attester.as_ref(),
+    )?;
+
+    let referral_program = &ctx.accounts.referral_program;
+    let referral = &mut ctx.accounts.referral;
+    let referral_code = &mut ctx.accounts.referral_code;
+    require!(!referral.credited, CepError::ReferralAlreadyCredited);
+    require!(
+        referral_code.credited_count < referral_program.max_referrals,
+        CepError::ReferralCapReached
+    );
+    referral.credited = true;
+    referral_code.credited_count += 1;
+
+    let clock = Clock::get()?;
+    credit_award_points(
+        referral_program.brand,
+        referral.referrer,
+        referral_program.points,
+        clock.unix_timestamp,
+        &mut ctx.accounts.referrer_points,
+        &ctx.accounts.leaderboard,
+        None,
+    )?;
+
+    if let Some(reward_key) = referral_program.reward {
+        let (Some(reward), Some(token_mint), Some(referrer_token_account)) = (
+            ctx.accounts.reward.as_mut(),
+            &ctx.accounts.token_mint,
+            &ctx.accounts.referrer_token_account,
+        ) else {
+            return Err(CepError::RewardAccountsRequired.into());
+        };
+        require_keys_eq!(reward.key(), reward_key, CepError::ReferralRewardMismatch);
+        mint_reward_tokens(
+            reward,
+            token_mint,
+            &ctx.accounts.referrer,
+            referrer_token_account,
+            &ctx.accounts.authority,
+            &ctx.accounts.token_program,
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.system_program,
+            referral_program.reward_amount,
+        )?;
+    }
+
+    msg!(
+        "Referrer {} credited for referring user {}",
+        referral.referrer,
+        referral.referred
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ConfigureReferrals<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ReferralProgram::SPACE,
+        seeds = [b"referral-program", brand.key().as_ref()],
+        bump
+    )]
+    pub referral_program: Account<'info, ReferralProgram>,
+    /// Fungible reward issued to referrers on top of the points
+    #[account(has_one = brand)]
+    pub reward: Option<Account<'info, Reward>>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct RegisterReferralCode<'info> {
+    pub brand: Account<'info, Brand>,
+    #[account(
+        init,
+        payer = referrer,
+        space = ReferralCode::SPACE,
+        seeds = [b"referral-code", brand.key().as_ref(), code.as_bytes()],
+        bump
+    )]
+    pub referral_code: Account<'info, ReferralCode>,
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreditReferral<'info> {
+    pub brand: Account<'info, Brand>,
+    #[account(seeds = [b"referral-program", brand.key().as_ref()], bump)]
+    pub referral_program: Account<'info, ReferralProgram>,
+    #[account(mut, has_one = brand, has_one = referrer)]
+    pub referral: Account<'info, Referral>,
+    #[account(mut, address = referral.code)]
+    pub referral_code: Account<'info, ReferralCode>,
+    /// CHECK: The referrer credited, checked against the referral
+    pub referrer: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = UserPoints::SPACE,
+        seeds = [b"user-points", brand.key().as_ref(), referrer.key().as_ref()],
+        bump
+    )]
+    pub referrer_points: Account<'info, UserPoints>,
+    /// CHECK: The brand's leaderboard, ranked by lifetime points, updated
+    /// once the brand has created it
+    #[account(mut, seeds = [b"leaderboard", brand.key().as_ref()], bump)]
+    pub leaderboard: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// Required when `authority` credits referrals as a delegated attester
+    pub attester: Option<Account<'info, Attester>>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    /// The referral program's reward, required when it has one
+    #[account(mut)]
+    pub reward: Option<Account<'info, Reward>>,
+    #[account(mut)]
+    pub token_mint: Option<Account<'info, Mint>>,
+    /// CHECK: The referrer's associated token account for the reward, created if missing
+    #[account(mut)]
+    pub referrer_token_account: Option<UncheckedAccount<'info>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+/// What a brand's referrers earn for each qualified referral.
+#[account]
+pub struct ReferralProgram {
+    pub brand: Pubkey,
+    pub points: u64,            // Points credited to the referrer
+    pub reward: Option<Pubkey>, // Fungible reward issued to the referrer
+    pub reward_amount: u64,
+    pub max_referrals: u32, // Qualified referrals credited per referrer
+    pub updated_at: i64,
+}
+
+impl ReferralProgram {
+    pub const SPACE: usize = 8 // Discriminator
+        + 32 // Brand
+        + 8 // Points
+        + 1 + 32 // Reward
+        + 8 // Reward amount
+        + 4 // Max referrals
+        + 8; // Updated at
+}
+
+/// A referrer's code with a brand, at `[b"referral-code", brand, code]`.
+#[account]
+pub struct ReferralCode {
+    pub brand: Pubkey,
+    pub referrer: Pubkey,
+    pub code: String,
+    pub referred_count: u32, // Members who joined with the code
+    pub credited_count: u32, // Referrals the referrer has been credited for
+    pub created_at: i64,
+}
+
+impl ReferralCode {
+    pub const MAX_CODE_LENGTH: usize = 32;
+
+    pub const SPACE: usize = 8 // Discriminator
+        + 32 // Brand
+        + 32 // Referrer
+        + 4 + Self::MAX_CODE_LENGTH // Code
+        + 4 // Referred count
+        + 4 // Credited count
+        + 8; // Created at
+}
+
+/// Records who referred a member to a brand, at `[b"referral", brand, referred]`.
+#[account]
+#[derive(Default)]
+pub struct Referral {
+    pub brand: Pubkey,
+    pub referrer: Pubkey,
+    pub referred: Pubkey,
+    pub code: Pubkey,
+    pub credited: bool,
+    pub recorded_at: i64,
+}
+
+impl Referral {
+    pub const SPACE: usize = 8 // Discriminator
+        + 32 // Brand
+        + 32 // Referrer
+        + 32 // Referred
+        + 32 // Code
+        + 1 // Credited
+        + 8; // Recorded at
+}
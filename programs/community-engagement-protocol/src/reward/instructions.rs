@@ -1,10 +1,13 @@
 use super::state::*;
 use crate::brand::state::Brand;
 use crate::errors::CepError;
+use crate::token_extensions::{NewMint, TokenMetadataArgs};
 use crate::ProgramState;
 use anchor_lang::prelude::*;
-use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::associated_token::{self, get_associated_token_address, AssociatedToken};
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_2022;
+use anchor_spl::token_interface::{self, TokenInterface};
 
 pub fn create_fungible_reward(
     ctx: Context<CreateFungibleReward>,
@@ -32,6 +35,9 @@ pub fn create_fungible_reward(
     };
     reward.created_at = clock.unix_timestamp;
     reward.updated_at = clock.unix_timestamp;
+    reward.active = true;
+    reward.starts_at = None;
+    reward.ends_at = None;
 
     Ok(())
 }
@@ -41,6 +47,7 @@ pub fn create_non_fungible_reward(
     name: String,
     description: String,
     metadata_uri: String,
+    token_metadata: Option<TokenMetadataArgs>,
 ) -> Result<()> {
     let reward = &mut ctx.accounts.reward;
     let brand = &ctx.accounts.brand;
@@ -56,6 +63,16 @@ pub fn create_non_fungible_reward(
         return Err(CepError::UriTooLong.into());
     }
 
+    NewMint {
+        token_program: &ctx.accounts.token_program.to_account_info(),
+        system_program: &ctx.accounts.system_program.to_account_info(),
+        payer: &ctx.accounts.tronic_admin.to_account_info(),
+        mint: &ctx.accounts.token_mint.to_account_info(),
+        authority: &ctx.accounts.tronic_admin.to_account_info(),
+        signer_seeds: &[],
+    }
+    .create(0, token_metadata.map(|args| (name.clone(), args)), None)?;
+
     reward.brand = brand.key();
     reward.name = name;
     reward.description = description;
@@ -66,27 +83,19 @@ pub fn create_non_fungible_reward(
     reward.created_at = clock.unix_timestamp;
     reward.updated_at = clock.unix_timestamp;
     reward.issued_count = 0; // Initialize issued_count
+    reward.active = true;
+    reward.starts_at = None;
+    reward.ends_at = None;
 
     Ok(())
 }
 
 pub fn issue_fungible_reward(ctx: Context<IssueFungibleReward>, amount: u64) -> Result<()> {
-    let reward = &ctx.accounts.reward;
-
-    // Ensure the reward is fungible
-    if let RewardType::Fungible {
-        token_mint,
-        token_supply,
-    } = reward.reward_type
-    {
-        require!(
-            token_mint == ctx.accounts.token_mint.key(),
-            CepError::InvalidRewardType
-        );
-        require!(amount <= token_supply, CepError::InsufficientRewardSupply);
-    } else {
-        return Err(CepError::InvalidRewardType.into());
-    }
+    let reward = &mut ctx.accounts.reward;
+    let clock = Clock::get()?;
+
+    reward.check_issuable(clock.unix_timestamp)?;
+    reward.consume_supply(&ctx.accounts.token_mint.key(), amount)?;
 
     // Mint tokens to the user's account
     token::mint_to(
@@ -104,11 +113,143 @@ pub fn issue_fungible_reward(ctx: Context<IssueFungibleReward>, amount: u64) ->
     Ok(())
 }
 
+/// Issues `amount` of a fungible reward to the user's associated token
+/// account, creating it if missing. `authority` has to be the reward's mint
+/// authority.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn mint_reward_tokens<'info>(
+    reward: &mut Account<'info, Reward>,
+    token_mint: &Account<'info, Mint>,
+    user: &AccountInfo<'info>,
+    user_token_account: &AccountInfo<'info>,
+    authority: &Signer<'info>,
+    token_program: &Program<'info, Token>,
+    associated_token_program: &Program<'info, AssociatedToken>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    reward.check_issuable(Clock::get()?.unix_timestamp)?;
+    reward.consume_supply(&token_mint.key(), amount)?;
+
+    require_keys_eq!(
+        user_token_account.key(),
+        get_associated_token_address(user.key, &token_mint.key()),
+        CepError::RewardAccountsRequired
+    );
+    if user_token_account.data_is_empty() {
+        associated_token::create(CpiContext::new(
+            associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: authority.to_account_info(),
+                associated_token: user_token_account.clone(),
+                authority: user.clone(),
+                mint: token_mint.to_account_info(),
+                system_program: system_program.to_account_info(),
+                token_program: token_program.to_account_info(),
+            },
+        ))?;
+    }
+
+    token::mint_to(
+        CpiContext::new(
+            token_program.to_account_info(),
+            token::MintTo {
+                mint: token_mint.to_account_info(),
+                to: user_token_account.clone(),
+                authority: authority.to_account_info(),
+            },
+        ),
+        amount,
+    )
+}
+
+/// Recipients per batch issue, each passed as a (user, user_token_account)
+/// pair in `remaining_accounts`.
+pub const MAX_BATCH_ISSUE_SIZE: usize = 12;
+
+/// Mints `amounts[i]` to the i-th recipient. Unlike the achievement batch this
+/// is all-or-nothing: any bad pair or a total above the remaining supply
+/// fails the whole transaction.
+pub fn batch_issue_fungible_reward<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchIssueFungibleReward<'info>>,
+    amounts: Vec<u64>,
+) -> Result<()> {
+    let remaining = ctx.remaining_accounts;
+    let clock = Clock::get()?;
+
+    require!(
+        remaining.len() == amounts.len() * 2,
+        CepError::InvalidBatchAccounts
+    );
+    require!(
+        amounts.len() <= MAX_BATCH_ISSUE_SIZE,
+        CepError::BatchTooLarge
+    );
+
+    let total = amounts
+        .iter()
+        .try_fold(0u64, |total, amount| total.checked_add(*amount))
+        .ok_or(CepError::InsufficientRewardSupply)?;
+
+    let reward = &mut ctx.accounts.reward;
+    reward.check_issuable(clock.unix_timestamp)?;
+    reward.consume_supply(&ctx.accounts.token_mint.key(), total)?;
+
+    let token_mint = ctx.accounts.token_mint.to_account_info();
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let tronic_admin = ctx.accounts.tronic_admin.to_account_info();
+
+    for (pair, amount) in remaining.chunks(2).zip(amounts) {
+        let (user, user_token_account) = (&pair[0], &pair[1]);
+        require_keys_eq!(
+            user_token_account.key(),
+            get_associated_token_address(user.key, &token_mint.key()),
+            CepError::InvalidBatchAccounts
+        );
+
+        if user_token_account.data_is_empty() {
+            associated_token::create(CpiContext::new(
+                ctx.accounts.associated_token_program.to_account_info(),
+                associated_token::Create {
+                    payer: tronic_admin.clone(),
+                    associated_token: user_token_account.clone(),
+                    authority: user.clone(),
+                    mint: token_mint.clone(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: token_program.clone(),
+                },
+            ))?;
+        }
+
+        token::mint_to(
+            CpiContext::new(
+                token_program.clone(),
+                token::MintTo {
+                    mint: token_mint.clone(),
+                    to: user_token_account.clone(),
+                    authority: tronic_admin.clone(),
+                },
+            ),
+            amount,
+        )?;
+    }
+
+    msg!(
+        "Issued {} tokens of reward '{}' to {} recipients",
+        total,
+        reward.name,
+        remaining.len() / 2
+    );
+    Ok(())
+}
+
 pub fn issue_non_fungible_reward(ctx: Context<IssueNonFungibleReward>) -> Result<()> {
     let reward = &mut ctx.accounts.reward;
     let instance = &mut ctx.accounts.reward_instance;
     let clock = Clock::get()?;
 
+    reward.check_issuable(clock.unix_timestamp)?;
+
     if let RewardType::NonFungible { token_mint, .. } = reward.reward_type {
         require!(
             token_mint == ctx.accounts.token_mint.key(),
@@ -127,10 +268,10 @@ pub fn issue_non_fungible_reward(ctx: Context<IssueNonFungibleReward>) -> Result
     instance.issued_at = clock.unix_timestamp;
 
     // Mint the NFT to the user's account
-    token::mint_to(
+    token_interface::mint_to(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            token::MintTo {
+            token_interface::MintTo {
                 mint: ctx.accounts.token_mint.to_account_info(),
                 to: ctx.accounts.user_token_account.to_account_info(),
                 authority: ctx.accounts.tronic_admin.to_account_info(),
@@ -142,6 +283,101 @@ pub fn issue_non_fungible_reward(ctx: Context<IssueNonFungibleReward>) -> Result
     Ok(())
 }
 
+/// Updates a reward's details. Token-2022 rewards keep their name and URI
+/// in the mint's TokenMetadata as well, so only their description can change
+/// here.
+pub fn update_reward(
+    ctx: Context<UpdateRewardDetails>,
+    name: String,
+    description: String,
+    metadata_uri: Option<String>,
+) -> Result<()> {
+    let reward = &mut ctx.accounts.reward;
+    let clock = Clock::get()?;
+
+    let (RewardType::Fungible { token_mint, .. } | RewardType::NonFungible { token_mint, .. }) =
+        &reward.reward_type;
+    require_keys_eq!(
+        ctx.accounts.token_mint.key(),
+        *token_mint,
+        CepError::InvalidRewardType
+    );
+    if *ctx.accounts.token_mint.owner == token_2022::ID {
+        require!(
+            name == reward.name && metadata_uri.is_none(),
+            CepError::RewardMetadataOnMint
+        );
+    }
+
+    if name.chars().count() > 50 {
+        return Err(CepError::NameTooLong.into());
+    }
+    if description.chars().count() > 200 {
+        return Err(CepError::DescriptionTooLong.into());
+    }
+
+    if let Some(new_uri) = metadata_uri {
+        if new_uri.chars().count() > 200 {
+            return Err(CepError::UriTooLong.into());
+        }
+        // Only non-fungible rewards carry a metadata URI
+        match &mut reward.reward_type {
+            RewardType::NonFungible { metadata_uri, .. } => *metadata_uri = new_uri,
+            RewardType::Fungible { .. } => return Err(CepError::InvalidRewardType.into()),
+        }
+    }
+
+    reward.name = name;
+    reward.description = description;
+    reward.updated_at = clock.unix_timestamp;
+
+    Ok(())
+}
+
+pub fn set_reward_active(ctx: Context<UpdateReward>, active: bool) -> Result<()> {
+    let reward = &mut ctx.accounts.reward;
+    let clock = Clock::get()?;
+
+    reward.active = active;
+    reward.updated_at = clock.unix_timestamp;
+
+    msg!("Reward '{}' active: {}", reward.name, active);
+    Ok(())
+}
+
+pub fn set_reward_schedule(
+    ctx: Context<UpdateReward>,
+    starts_at: Option<i64>,
+    ends_at: Option<i64>,
+) -> Result<()> {
+    let reward = &mut ctx.accounts.reward;
+    let clock = Clock::get()?;
+
+    if let (Some(starts_at), Some(ends_at)) = (starts_at, ends_at) {
+        require!(starts_at < ends_at, CepError::InvalidSchedule);
+    }
+
+    reward.starts_at = starts_at;
+    reward.ends_at = ends_at;
+    reward.updated_at = clock.unix_timestamp;
+
+    Ok(())
+}
+
+pub fn close_reward(ctx: Context<CloseReward>) -> Result<()> {
+    msg!("Reward '{}' closed", ctx.accounts.reward.name);
+    Ok(())
+}
+
+pub fn close_reward_instance(ctx: Context<CloseRewardInstance>) -> Result<()> {
+    msg!(
+        "Reward instance {} of reward '{}' closed",
+        ctx.accounts.reward_instance.token_id,
+        ctx.accounts.reward.name
+    );
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct CreateFungibleReward<'info> {
     #[account(mut)]
@@ -158,6 +394,10 @@ pub struct CreateFungibleReward<'info> {
             + 8 // Token supply
             + 8 // Created at
             + 8 // Updated at
+            + 8 // Issued count
+            + 1 // Active
+            + (1 + 8) // Starts at
+            + (1 + 8) // Ends at
     )]
     pub reward: Account<'info, Reward>,
 
@@ -202,17 +442,15 @@ pub struct CreateNonFungibleReward<'info> {
             + 8 // Created at
             + 8 // Updated at
             + 8 // Issued count
+            + 1 // Active
+            + (1 + 8) // Starts at
+            + (1 + 8) // Ends at
     )]
     pub reward: Account<'info, Reward>,
 
-    #[account(
-        init,
-        payer = tronic_admin,
-        mint::decimals = 0,
-        mint::authority = tronic_admin.key(),
-        mint::freeze_authority = tronic_admin.key(),
-    )]
-    pub token_mint: Account<'info, Mint>,
+    /// Created in the instruction so it can be owned by either token program
+    #[account(mut)]
+    pub token_mint: Signer<'info>,
 
     #[account(
         seeds = [b"program-state"],
@@ -225,7 +463,7 @@ pub struct CreateNonFungibleReward<'info> {
     pub tronic_admin: Signer<'info>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub rent: Sysvar<'info, Rent>,
 }
 
@@ -260,6 +498,26 @@ pub struct IssueFungibleReward<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[derive(Accounts)]
+pub struct BatchIssueFungibleReward<'info> {
+    pub brand: Account<'info, Brand>,
+    #[account(mut, has_one = brand)]
+    pub reward: Account<'info, Reward>,
+    #[account(
+        seeds = [b"program-state"],
+        bump,
+        constraint = program_state.tronic_admin == tronic_admin.key() @ CepError::UnauthorizedTronicAdmin
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    #[account(mut)]
+    pub tronic_admin: Signer<'info>,
+    #[account(mut)]
+    pub token_mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct IssueNonFungibleReward<'info> {
     #[account(mut)]
@@ -287,16 +545,79 @@ pub struct IssueNonFungibleReward<'info> {
     #[account(mut)]
     pub tronic_admin: Signer<'info>,
     #[account(mut)]
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, token_interface::Mint>,
     #[account(
         init_if_needed,
         payer = tronic_admin,
         associated_token::mint = token_mint,
         associated_token::authority = user,
+        associated_token::token_program = token_program,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+    pub user_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
+
+#[derive(Accounts)]
+pub struct UpdateReward<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(mut, has_one = brand)]
+    pub reward: Account<'info, Reward>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRewardDetails<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(mut, has_one = brand)]
+    pub reward: Account<'info, Reward>,
+    /// CHECK: The reward's mint, only read for the program that owns it
+    pub token_mint: UncheckedAccount<'info>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseReward<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(mut, has_one = brand, close = recipient)]
+    pub reward: Account<'info, Reward>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    pub authority: Signer<'info>,
+    /// CHECK: Only receives the reclaimed rent
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRewardInstance<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(has_one = brand)]
+    pub reward: Account<'info, Reward>,
+    #[account(mut, has_one = reward, close = recipient)]
+    pub reward_instance: Account<'info, NonFungibleRewardInstance>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    pub authority: Signer<'info>,
+    /// CHECK: Only receives the reclaimed rent
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
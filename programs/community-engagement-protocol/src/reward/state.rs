@@ -1,3 +1,4 @@
+use crate::errors::CepError;
 use anchor_lang::prelude::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
@@ -18,9 +19,42 @@ pub struct Reward {
     pub name: String,
     pub description: String,
     pub reward_type: RewardType,
-    pub created_at: i64,   // Time this reward was created
-    pub updated_at: i64,   // Last time this reward was updated
-    pub issued_count: u64, // Number of times this reward has been issued
+    pub created_at: i64,        // Time this reward was created
+    pub updated_at: i64,        // Last time this reward was updated
+    pub issued_count: u64,      // Number of times this reward has been issued
+    pub active: bool,           // Whether this reward can currently be issued
+    pub starts_at: Option<i64>, // Reward can't be issued before this time
+    pub ends_at: Option<i64>,   // Reward can't be issued after this time
+}
+
+impl Reward {
+    /// Checks that the reward is active and inside its schedule window.
+    pub fn check_issuable(&self, now: i64) -> Result<()> {
+        require!(self.active, CepError::RewardInactive);
+        if let Some(starts_at) = self.starts_at {
+            require!(now >= starts_at, CepError::RewardNotStarted);
+        }
+        if let Some(ends_at) = self.ends_at {
+            require!(now <= ends_at, CepError::RewardEnded);
+        }
+        Ok(())
+    }
+
+    /// Takes `amount` out of a fungible reward's remaining supply.
+    pub fn consume_supply(&mut self, mint: &Pubkey, amount: u64) -> Result<()> {
+        let RewardType::Fungible {
+            token_mint,
+            token_supply,
+        } = &mut self.reward_type
+        else {
+            return Err(CepError::InvalidRewardType.into());
+        };
+        require_keys_eq!(*token_mint, *mint, CepError::InvalidRewardType);
+        *token_supply = token_supply
+            .checked_sub(amount)
+            .ok_or(CepError::InsufficientRewardSupply)?;
+        Ok(())
+    }
 }
 
 #[account]
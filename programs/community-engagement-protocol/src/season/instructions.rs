@@ -0,0 +1,172 @@
+use super::state::*;
+use crate::brand::state::Brand;
+use crate::errors::CepError;
+use crate::points::state::Standing;
+use crate::reward::instructions::mint_reward_tokens;
+use crate::reward::state::Reward;
+use crate::ProgramState;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token};
+
+pub fn create_season(
+    ctx: Context<CreateSeason>,
+    season_id: u32,
+    starts_at: i64,
+    ends_at: i64,
+) -> Result<()> {
+    require!(starts_at < ends_at, CepError::InvalidSchedule);
+
+    let season = &mut ctx.accounts.season;
+    season.brand = ctx.accounts.brand.key();
+    season.season_id = season_id;
+    season.starts_at = starts_at;
+    season.ends_at = ends_at;
+    season.standings = vec![];
+    season.closed = false;
+
+    msg!(
+        "Season {} of Brand '{}' runs from {} to {}",
+        season_id,
+        ctx.accounts.brand.name,
+        starts_at,
+        ends_at
+    );
+    Ok(())
+}
+
+/// Snapshots the final standings into a `SeasonResult`. With a fungible
+/// reward, `rank_amounts[i]` tokens go to the user ranked `i`, passed as
+/// (user, user_token_account) pairs in rank order in `remaining_accounts`.
+pub fn close_season<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CloseSeason<'info>>,
+    rank_amounts: Vec<u64>,
+) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    let clock = Clock::get()?;
+
+    require!(!season.closed, CepError::SeasonClosed);
+    require!(
+        clock.unix_timestamp >= season.ends_at,
+        CepError::SeasonNotEnded
+    );
+    require!(
+        rank_amounts.len() <= Season::MAX_STANDINGS,
+        CepError::BatchTooLarge
+    );
+
+    let rewarded = rank_amounts.len().min(season.standings.len());
+    if rewarded > 0 {
+        let (Some(reward), Some(token_mint)) =
+            (ctx.accounts.reward.as_mut(), &ctx.accounts.token_mint)
+        else {
+            return Err(CepError::RewardAccountsRequired.into());
+        };
+        require!(
+            ctx.remaining_accounts.len() == rewarded * 2,
+            CepError::InvalidBatchAccounts
+        );
+
+        for ((standing, pair), amount) in season
+            .standings
+            .iter()
+            .zip(ctx.remaining_accounts.chunks(2))
+            .zip(&rank_amounts)
+        {
+            let (user, user_token_account) = (&pair[0], &pair[1]);
+            require_keys_eq!(user.key(), standing.user, CepError::InvalidBatchAccounts);
+            mint_reward_tokens(
+                reward,
+                token_mint,
+                user,
+                user_token_account,
+                &ctx.accounts.authority,
+                &ctx.accounts.token_program,
+                &ctx.accounts.associated_token_program,
+                &ctx.accounts.system_program,
+                *amount,
+            )?;
+        }
+    }
+
+    let result = &mut ctx.accounts.season_result;
+    result.brand = season.brand;
+    result.season = season.key();
+    result.season_id = season.season_id;
+    result.standings = season.standings.clone();
+    result.rewarded = rewarded as u8;
+    result.closed_at = clock.unix_timestamp;
+    season.closed = true;
+
+    msg!(
+        "Season {} closed with {} ranked users, {} rewarded",
+        season.season_id,
+        result.standings.len(),
+        rewarded
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(season_id: u32)]
+pub struct CreateSeason<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 // Discriminator
+            + 32 // Brand
+            + 4 // Season id
+            + 8 // Starts at
+            + 8 // Ends at
+            + 4 + Standing::SPACE * Season::MAX_STANDINGS // Standings
+            + 1, // Closed
+        seeds = [b"season", brand.key().as_ref(), &season_id.to_le_bytes()],
+        bump
+    )]
+    pub season: Account<'info, Season>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseSeason<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(mut, has_one = brand)]
+    pub season: Account<'info, Season>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 // Discriminator
+            + 32 // Brand
+            + 32 // Season
+            + 4 // Season id
+            + 4 + Standing::SPACE * Season::MAX_STANDINGS // Standings
+            + 1 // Rewarded
+            + 8, // Closed at
+        seeds = [b"season-result", season.key().as_ref()],
+        bump
+    )]
+    pub season_result: Account<'info, SeasonResult>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// Fungible reward issued to the top ranks
+    #[account(mut, has_one = brand)]
+    pub reward: Option<Account<'info, Reward>>,
+    #[account(mut)]
+    pub token_mint: Option<Account<'info, Mint>>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
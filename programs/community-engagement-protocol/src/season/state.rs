@@ -0,0 +1,111 @@
+use crate::errors::CepError;
+use crate::points::state::Standing;
+use crate::utils::{load_or_create_account, store_account};
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Season {
+    pub brand: Pubkey,
+    pub season_id: u32,
+    pub starts_at: i64,
+    pub ends_at: i64,
+    pub standings: Vec<Standing>, // Top users of the season, best first
+    pub closed: bool,
+}
+
+impl Season {
+    pub const MAX_STANDINGS: usize = 10;
+
+    /// Adds `points` to the user's season total and their standing.
+    pub fn credit(
+        &mut self,
+        user_season_points: &mut UserSeasonPoints,
+        points: u64,
+        now: i64,
+    ) -> Result<()> {
+        require!(
+            !self.closed && (self.starts_at..self.ends_at).contains(&now),
+            CepError::SeasonNotActive
+        );
+
+        user_season_points.points = user_season_points.points.saturating_add(points);
+        Standing::update(
+            &mut self.standings,
+            user_season_points.user,
+            user_season_points.points,
+            Self::MAX_STANDINGS,
+        );
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct UserSeasonPoints {
+    pub season: Pubkey,
+    pub user: Pubkey,
+    pub points: u64,
+}
+
+impl UserSeasonPoints {
+    pub const SPACE: usize = 8 + 32 + 32 + 8;
+}
+
+/// Final standings of a closed season.
+#[account]
+pub struct SeasonResult {
+    pub brand: Pubkey,
+    pub season: Pubkey,
+    pub season_id: u32,
+    pub standings: Vec<Standing>,
+    pub rewarded: u8, // Number of top ranks that received a reward
+    pub closed_at: i64,
+}
+
+/// The season an award's points also count towards, with the user's
+/// `[b"user-season-points", season, user]` PDA.
+pub struct SeasonCredit<'a, 'info> {
+    pub season: &'a mut Account<'info, Season>,
+    pub user_season_points: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> SeasonCredit<'a, 'info> {
+    /// Pairs the optional season accounts of an award; they're passed
+    /// together or not at all.
+    pub fn new(
+        season: Option<&'a mut Account<'info, Season>>,
+        user_season_points: Option<&'a AccountInfo<'info>>,
+        payer: &'a AccountInfo<'info>,
+        system_program: &'a AccountInfo<'info>,
+    ) -> Result<Option<Self>> {
+        match (season, user_season_points) {
+            (None, None) => Ok(None),
+            (Some(season), Some(user_season_points)) => Ok(Some(Self {
+                season,
+                user_season_points,
+                payer,
+                system_program,
+            })),
+            _ => Err(CepError::SeasonAccountsRequired.into()),
+        }
+    }
+
+    pub fn credit(self, brand: &Pubkey, user: Pubkey, points: u64, now: i64) -> Result<()> {
+        require_keys_eq!(self.season.brand, *brand, CepError::SeasonNotActive);
+
+        let season_key = self.season.key();
+        let mut user_season_points: UserSeasonPoints = load_or_create_account(
+            self.user_season_points,
+            &[b"user-season-points", season_key.as_ref(), user.as_ref()],
+            UserSeasonPoints::SPACE,
+            self.payer,
+            self.system_program,
+        )?;
+        user_season_points.season = season_key;
+        user_season_points.user = user;
+        self.season.credit(&mut user_season_points, points, now)?;
+        store_account(self.user_season_points, &user_season_points)
+    }
+}
@@ -0,0 +1,311 @@
+use super::state::*;
+use crate::achievement::instructions::{
+    create_badge_account_if_missing, create_user_achievement, mint_badge,
+};
+use crate::achievement::state::{
+    Achievement, UserAchievement, UserAchievements, MINT_AUTHORITY_SEED,
+};
+use crate::attester::state::{authorize_activity, authorize_award, Attester};
+use crate::brand::state::Brand;
+use crate::errors::CepError;
+use crate::points::state::{credit_award_points, UserPoints};
+use crate::season::state::{Season, SeasonCredit};
+use crate::ProgramState;
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+    token_interface::{Mint, TokenInterface},
+};
+
+pub fn create_streak(
+    ctx: Context<CreateStreak>,
+    name: String,
+    period: i64,
+    grace: i64,
+    milestones: Vec<StreakMilestone>,
+) -> Result<()> {
+    let streak = &mut ctx.accounts.streak;
+    let clock = Clock::get()?;
+
+    if name.chars().count() > 50 {
+        return Err(CepError::NameTooLong.into());
+    }
+    require!(
+        period > 0 && (0..period).contains(&grace),
+        CepError::InvalidStreakPeriod
+    );
+    require!(
+        milestones.len() <= Streak::MAX_MILESTONES,
+        CepError::InvalidStreakMilestones
+    );
+    for (index, milestone) in milestones.iter().enumerate() {
+        require!(
+            milestone.length > 0
+                && milestones[..index]
+                    .iter()
+                    .all(|other| other.length != milestone.length),
+            CepError::InvalidStreakMilestones
+        );
+    }
+
+    streak.brand = ctx.accounts.brand.key();
+    streak.name = name;
+    streak.period = period;
+    streak.grace = grace;
+    streak.starts_at = clock.unix_timestamp;
+    streak.milestones = milestones;
+    streak.created_at = clock.unix_timestamp;
+
+    msg!(
+        "Streak '{}' created with a period of {} seconds",
+        streak.name,
+        streak.period
+    );
+    Ok(())
+}
+
+/// Counts the user's activity for the current period. When the streak
+/// reaches a milestone, the milestone's achievement accounts are required
+/// and its badge is awarded unless the user already holds it or it isn't
+/// available. Prerequisite records for the milestone go in
+/// `remaining_accounts`.
+pub fn record_streak_activity(ctx: Context<RecordStreakActivity>) -> Result<()> {
+    let streak = &ctx.accounts.streak;
+    let user = ctx.accounts.user.key();
+    let clock = Clock::get()?;
+
+    let user_streak = &mut ctx.accounts.user_streak;
+    user_streak.streak = streak.key();
+    user_streak.user = user;
+    let counted = user_streak.record(streak, clock.unix_timestamp);
+    let milestone = streak
+        .milestone(user_streak.current)
+        .filter(|_| counted)
+        .map(|milestone| milestone.achievement);
+
+    msg!(
+        "Streak '{}' for user {} is at {}",
+        streak.name,
+        user,
+        user_streak.current
+    );
+
+    let Some(milestone) = milestone else {
+        return authorize_activity(
+            &ctx.accounts.brand,
+            &ctx.accounts.authority.key(),
+            &ctx.accounts.program_state.tronic_admin,
+            ctx.accounts.attester.as_ref(),
+        );
+    };
+
+    let (
+        Some(achievement),
+        Some(user_achievements),
+        Some(user_achievement),
+        Some(token_mint),
+        Some(user_token_account),
+        Some(token_program),
+    ) = (
+        ctx.accounts.milestone_achievement.as_mut(),
+        ctx.accounts.user_achievements.as_mut(),
+        &ctx.accounts.user_achievement,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.user_token_account,
+        &ctx.accounts.token_program,
+    )
+    else {
+        return Err(CepError::MilestoneAccountsRequired.into());
+    };
+    require_keys_eq!(
+        achievement.key(),
+        milestone,
+        CepError::MilestoneAccountsRequired
+    );
+    require!(
+        achievement.token_mint == Some(token_mint.key()),
+        CepError::InvalidAchievementType
+    );
+    require_keys_eq!(
+        user_achievements.user,
+        user,
+        CepError::MilestoneAccountsRequired
+    );
+    let (expected_record, bump) = Pubkey::find_program_address(
+        &[b"user-achievement", milestone.as_ref(), user.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(
+        user_achievement.key(),
+        expected_record,
+        CepError::MilestoneAccountsRequired
+    );
+    require_keys_eq!(
+        user_token_account.key(),
+        get_associated_token_address_with_program_id(
+            &user,
+            &token_mint.key(),
+            &token_program.key()
+        ),
+        CepError::MilestoneAccountsRequired
+    );
+
+    // Users who already hold the badge, e.g. after a reset, aren't awarded again
+    let awards = user_achievement.data_is_empty()
+        && !achievement.archived
+        && achievement.has_prerequisites(&user, ctx.remaining_accounts)
+        && achievement.record_awards(clock.unix_timestamp, 1).is_ok();
+    authorize_award(
+        &ctx.accounts.brand,
+        &milestone,
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.program_state.tronic_admin,
+        ctx.accounts.attester.as_mut(),
+        u32::from(awards),
+    )?;
+    if !awards {
+        return Ok(());
+    }
+
+    create_user_achievement(
+        user_achievement,
+        bump,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+        &UserAchievement {
+            user,
+            achievement: milestone,
+            brand: achievement.brand,
+            awarded_at: clock.unix_timestamp,
+        },
+    )?;
+    user_achievements.achievements.push(milestone);
+
+    create_badge_account_if_missing(
+        user_token_account,
+        &ctx.accounts.user.to_account_info(),
+        token_mint,
+        &ctx.accounts.authority,
+        token_program,
+        &ctx.accounts.associated_token_program,
+        &ctx.accounts.system_program,
+    )?;
+    mint_badge(
+        token_program,
+        token_mint,
+        user_token_account,
+        &ctx.accounts.mint_authority,
+        &[
+            MINT_AUTHORITY_SEED,
+            ctx.accounts.brand.key().as_ref(),
+            &[ctx.bumps.mint_authority],
+        ],
+        &ctx.accounts.authority,
+        achievement.soulbound,
+    )?;
+
+    credit_award_points(
+        achievement.brand,
+        user,
+        achievement.points.into(),
+        clock.unix_timestamp,
+        &mut ctx.accounts.user_points,
+        &ctx.accounts.leaderboard,
+        SeasonCredit::new(
+            ctx.accounts.season.as_mut(),
+            ctx.accounts.user_season_points.as_deref(),
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?,
+    )?;
+
+    msg!("Milestone '{}' awarded to user {}", achievement.name, user);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateStreak<'info> {
+    #[account(
+        constraint = brand.is_authority(&authority.key(), &program_state.tronic_admin) @ CepError::Unauthorized
+    )]
+    pub brand: Account<'info, Brand>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 // Discriminator
+            + 32 // Brand
+            + 4 + 50 // Name
+            + 8 // Period
+            + 8 // Grace
+            + 8 // Starts at
+            + 4 + (4 + 32) * Streak::MAX_MILESTONES // Milestones
+            + 8 // Created at
+    )]
+    pub streak: Account<'info, Streak>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RecordStreakActivity<'info> {
+    pub brand: Account<'info, Brand>,
+    #[account(has_one = brand)]
+    pub streak: Account<'info, Streak>,
+    /// CHECK: This account is used to store the public key of the active user
+    pub user: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + 32 + 32 + 4 + 4 + 8 + 8,
+        seeds = [b"user-streak", streak.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_streak: Account<'info, UserStreak>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = UserPoints::SPACE,
+        seeds = [b"user-points", brand.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_points: Account<'info, UserPoints>,
+    /// CHECK: The brand's leaderboard, ranked by lifetime points, updated
+    /// once the brand has created it
+    #[account(mut, seeds = [b"leaderboard", brand.key().as_ref()], bump)]
+    pub leaderboard: UncheckedAccount<'info>,
+    /// Season the milestone's points also count towards
+    #[account(mut, has_one = brand)]
+    pub season: Option<Account<'info, Season>>,
+    /// CHECK: The user's points PDA for `season`, created on first credit
+    #[account(mut)]
+    pub user_season_points: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// Required when `authority` records activity as a delegated attester
+    #[account(mut)]
+    pub attester: Option<Account<'info, Attester>>,
+    #[account(seeds = [b"program-state"], bump)]
+    pub program_state: Account<'info, ProgramState>,
+    /// Achievement of the milestone reached by this activity
+    #[account(mut, constraint = milestone_achievement.brand == brand.key())]
+    pub milestone_achievement: Option<Account<'info, Achievement>>,
+    #[account(mut)]
+    pub user_achievements: Option<Account<'info, UserAchievements>>,
+    /// CHECK: Award record PDA, checked and created in the instruction
+    #[account(mut)]
+    pub user_achievement: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    /// CHECK: The brand's PDA that mints and freezes badges
+    #[account(seeds = [MINT_AUTHORITY_SEED, brand.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+    /// CHECK: The user's associated token account, checked and created in the instruction
+    #[account(mut)]
+    pub user_token_account: Option<UncheckedAccount<'info>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct StreakMilestone {
+    pub length: u32,         // Consecutive periods needed
+    pub achievement: Pubkey, // Fungible achievement awarded on reaching it
+}
+
+#[account]
+pub struct Streak {
+    pub brand: Pubkey,
+    pub name: String,
+    pub period: i64,    // Period length in seconds, e.g. 86400 for daily
+    pub grace: i64,     // Seconds into the following period a missed one can be made up
+    pub starts_at: i64, // Periods are counted from here
+    pub milestones: Vec<StreakMilestone>,
+    pub created_at: i64,
+}
+
+impl Streak {
+    pub const MAX_MILESTONES: usize = 5;
+
+    /// Index of the period `now` falls in.
+    pub fn period_index(&self, now: i64) -> i64 {
+        (now - self.starts_at).div_euclid(self.period)
+    }
+
+    pub fn milestone(&self, length: u32) -> Option<&StreakMilestone> {
+        self.milestones
+            .iter()
+            .find(|milestone| milestone.length == length)
+    }
+}
+
+#[account]
+pub struct UserStreak {
+    pub streak: Pubkey,
+    pub user: Pubkey,
+    pub current: u32,          // Consecutive periods with activity
+    pub longest: u32,          // Best run so far
+    pub last_period: i64,      // Period of the latest counted activity
+    pub last_activity_at: i64, // Time of the latest counted activity
+}
+
+impl UserStreak {
+    /// Counts activity at `now`, returning `false` if this period was
+    /// already counted. A period without activity resets the streak, unless
+    /// it's made up within the grace window of the next one.
+    pub fn record(&mut self, streak: &Streak, now: i64) -> bool {
+        let period = streak.period_index(now);
+        if self.current > 0 && period == self.last_period {
+            return false;
+        }
+
+        let period_start = streak.starts_at + period * streak.period;
+        let continues = self.current > 0
+            && (period == self.last_period + 1
+                || (period == self.last_period + 2 && now - period_start < streak.grace));
+
+        self.current = if continues { self.current + 1 } else { 1 };
+        self.longest = self.longest.max(self.current);
+        self.last_period = period;
+        self.last_activity_at = now;
+        true
+    }
+}
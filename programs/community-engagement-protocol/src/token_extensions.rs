@@ -0,0 +1,162 @@
+// File: src/token_extensions.rs
+
+use crate::errors::CepError;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{create_account, CreateAccount};
+use anchor_spl::token_2022::spl_token_2022::{
+    self, extension::ExtensionType, state::Mint as MintState,
+};
+use anchor_spl::token_interface::{
+    initialize_mint2, metadata_pointer_initialize, non_transferable_mint_initialize,
+    permanent_delegate_initialize, spl_pod::optional_keys::OptionalNonZeroPubkey,
+    spl_token_metadata_interface::state::TokenMetadata, token_metadata_initialize, InitializeMint2,
+    MetadataPointerInitialize, NonTransferableMintInitialize, PermanentDelegateInitialize,
+    TokenMetadataInitialize,
+};
+
+/// Symbol and URI stored on a Token-2022 mint through the TokenMetadata extension.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TokenMetadataArgs {
+    pub symbol: String,
+    pub uri: String,
+}
+
+/// Accounts needed to create a mint owned by either the SPL Token or the
+/// Token-2022 program. `authority` becomes the mint, freeze and metadata
+/// update authority, signing with `signer_seeds` when it is a PDA.
+pub struct NewMint<'a, 'info> {
+    pub token_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub payer: &'a AccountInfo<'info>,
+    pub mint: &'a AccountInfo<'info>,
+    pub authority: &'a AccountInfo<'info>,
+    pub signer_seeds: &'a [&'a [&'a [u8]]],
+}
+
+pub fn is_token_2022(token_program: &AccountInfo) -> bool {
+    token_program.key() == spl_token_2022::ID
+}
+
+impl<'a, 'info> NewMint<'a, 'info> {
+    /// Creates and initializes the mint. Metadata and non-transferability are
+    /// Token-2022 extensions, so asking for them on an SPL Token mint fails.
+    /// Non-transferable mints name the permanent delegate that can still burn
+    /// tokens to revoke them.
+    pub fn create(
+        &self,
+        decimals: u8,
+        metadata: Option<(String, TokenMetadataArgs)>,
+        non_transferable: Option<Pubkey>,
+    ) -> Result<()> {
+        let mut extensions = Vec::new();
+        if metadata.is_some() {
+            extensions.push(ExtensionType::MetadataPointer);
+        }
+        if non_transferable.is_some() {
+            extensions.push(ExtensionType::NonTransferable);
+            extensions.push(ExtensionType::PermanentDelegate);
+        }
+        require!(
+            extensions.is_empty() || is_token_2022(self.token_program),
+            CepError::Token2022Required
+        );
+
+        let token_metadata = metadata.map(|(name, args)| TokenMetadata {
+            update_authority: OptionalNonZeroPubkey(self.authority.key()),
+            mint: self.mint.key(),
+            name,
+            symbol: args.symbol,
+            uri: args.uri,
+            additional_metadata: vec![],
+        });
+
+        // The metadata is reallocated into the mint when it's initialized, so
+        // the account is funded for it up front but created without it
+        let space = ExtensionType::try_calculate_account_len::<MintState>(&extensions)?;
+        let metadata_space = match &token_metadata {
+            Some(token_metadata) => token_metadata.tlv_size_of()?,
+            None => 0,
+        };
+        let lamports = Rent::get()?.minimum_balance(space + metadata_space);
+
+        create_account(
+            CpiContext::new(
+                self.system_program.clone(),
+                CreateAccount {
+                    from: self.payer.clone(),
+                    to: self.mint.clone(),
+                },
+            ),
+            lamports,
+            space as u64,
+            self.token_program.key,
+        )?;
+
+        if token_metadata.is_some() {
+            metadata_pointer_initialize(
+                CpiContext::new(
+                    self.token_program.clone(),
+                    MetadataPointerInitialize {
+                        token_program_id: self.token_program.clone(),
+                        mint: self.mint.clone(),
+                    },
+                ),
+                Some(self.authority.key()),
+                Some(self.mint.key()),
+            )?;
+        }
+
+        if let Some(permanent_delegate) = non_transferable {
+            non_transferable_mint_initialize(CpiContext::new(
+                self.token_program.clone(),
+                NonTransferableMintInitialize {
+                    token_program_id: self.token_program.clone(),
+                    mint: self.mint.clone(),
+                },
+            ))?;
+            permanent_delegate_initialize(
+                CpiContext::new(
+                    self.token_program.clone(),
+                    PermanentDelegateInitialize {
+                        token_program_id: self.token_program.clone(),
+                        mint: self.mint.clone(),
+                    },
+                ),
+                &permanent_delegate,
+            )?;
+        }
+
+        initialize_mint2(
+            CpiContext::new(
+                self.token_program.clone(),
+                InitializeMint2 {
+                    mint: self.mint.clone(),
+                },
+            ),
+            decimals,
+            self.authority.key,
+            Some(self.authority.key),
+        )?;
+
+        if let Some(token_metadata) = token_metadata {
+            token_metadata_initialize(
+                CpiContext::new_with_signer(
+                    self.token_program.clone(),
+                    TokenMetadataInitialize {
+                        token_program_id: self.token_program.clone(),
+                        metadata: self.mint.clone(),
+                        update_authority: self.authority.clone(),
+                        mint_authority: self.authority.clone(),
+                        mint: self.mint.clone(),
+                    },
+                    self.signer_seeds,
+                ),
+                token_metadata.name,
+                token_metadata.symbol,
+                token_metadata.uri,
+            )?;
+        }
+
+        Ok(())
+    }
+}
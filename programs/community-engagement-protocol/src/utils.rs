@@ -0,0 +1,101 @@
+// File: src/utils.rs
+
+use crate::errors::CepError;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{
+    allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+};
+
+/// Reads the program account at `info`, creating it at the PDA of `seeds`
+/// first if it doesn't exist. Write it back with `store_account`.
+pub(crate) fn load_or_create_account<'info, T>(
+    info: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    space: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<T>
+where
+    T: AccountDeserialize + Default,
+{
+    let (address, bump) = Pubkey::find_program_address(seeds, &crate::ID);
+    require_keys_eq!(info.key(), address, CepError::InvalidProgramAccount);
+
+    if !info.data_is_empty() {
+        require_keys_eq!(*info.owner, crate::ID, CepError::InvalidProgramAccount);
+        return T::try_deserialize(&mut &info.try_borrow_data()?[..]);
+    }
+
+    let bump = [bump];
+    let signer_seeds = [seeds, &[&bump[..]]].concat();
+    create_program_account(info, &signer_seeds, space, payer, system_program)?;
+    Ok(T::default())
+}
+
+/// Creates a program-owned account of `space` bytes at the PDA signed for by
+/// `signer_seeds`. Like Anchor's `init`, an address that already holds
+/// lamports is topped up, allocated and assigned instead, since
+/// `create_account` refuses it.
+pub(crate) fn create_program_account<'info>(
+    info: &AccountInfo<'info>,
+    signer_seeds: &[&[u8]],
+    space: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let lamports = info.lamports();
+    if lamports == 0 {
+        return create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+                &[signer_seeds],
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let top_up = rent.saturating_sub(lamports);
+    if top_up > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate {
+                account_to_allocate: info.clone(),
+            },
+            &[signer_seeds],
+        ),
+        space as u64,
+    )?;
+    assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Assign {
+                account_to_assign: info.clone(),
+            },
+            &[signer_seeds],
+        ),
+        &crate::ID,
+    )
+}
+
+pub(crate) fn store_account<T: AccountSerialize>(info: &AccountInfo, account: &T) -> Result<()> {
+    account.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+declare_id!("DXuRfT3K8RsApmeeHC2t284yVK9LX8gdXyRHQpeL75oU");
+
+/// Stands in for a VRF oracle in local tests. It answers a raffle with the
+/// randomness the caller picks, in the layout raffles read.
+#[program]
+pub mod vrf_stub {
+    use super::*;
+
+    pub fn fulfill(ctx: Context<Fulfill>, raffle: Pubkey, randomness: [u8; 32]) -> Result<()> {
+        let result = &mut ctx.accounts.randomness;
+        result.raffle = raffle;
+        result.randomness = randomness;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(raffle: Pubkey)]
+pub struct Fulfill<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 32, // Discriminator + raffle + randomness
+        seeds = [b"randomness", raffle.as_ref()],
+        bump
+    )]
+    pub randomness: Account<'info, RandomnessResult>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[account]
+pub struct RandomnessResult {
+    pub raffle: Pubkey,
+    pub randomness: [u8; 32],
+}
@@ -1,7 +1,8 @@
 // tests/achievement_tests.ts
 import { expect } from 'chai';
 import * as anchor from "@coral-xyz/anchor";
-import { program, provider, brandList, createUniqueBrand, initializeProgramState, log, TOKEN_METADATA_PROGRAM_ID, TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, TRONIC_ADMIN_PUBKEY, TRONIC_ADMIN_KEYPAIR, fundAccount } from './common';
+import { ExtensionType, getAccount, getExtensionTypes, getMint, getTokenMetadata } from "@solana/spl-token";
+import { program, provider, brandList, createUniqueBrand, initializeProgramState, log, TOKEN_METADATA_PROGRAM_ID, TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, TRONIC_ADMIN_PUBKEY, TRONIC_ADMIN_KEYPAIR, fundAccount, BUBBLEGUM_PROGRAM_ID, ACCOUNT_COMPRESSION_PROGRAM_ID, findUserAchievementPda } from './common';
 
 describe("Achievement Tests", () => {
   before(initializeProgramState);
@@ -88,7 +89,6 @@ describe("Achievement Tests", () => {
     const tokenMint = anchor.web3.Keypair.generate();
     const user = anchor.web3.Keypair.generate();
     const userAchievements = anchor.web3.Keypair.generate();
-    const userAchievement = anchor.web3.Keypair.generate();
   
     log("Creating brand");
     const brandPda = await createUniqueBrand();
@@ -100,13 +100,16 @@ describe("Achievement Tests", () => {
         "A test fungible achievement",
         "Complete the test",
         100,
-        new anchor.BN(1000000)
+        new anchor.BN(1000000),
+        false,
+        null
       )
       .accounts({
         brand: brandPda,
         achievement: achievement.publicKey,
         tokenMint: tokenMint.publicKey,
         tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([achievement, tokenMint, TRONIC_ADMIN_KEYPAIR])
       .rpc();
@@ -135,15 +138,14 @@ describe("Achievement Tests", () => {
       .awardFungibleAchievement()
       .accounts({
         brand: brandPda,
-        userAchievement: userAchievement.publicKey,
         achievement: achievement.publicKey,
         user: user.publicKey,
         userAchievements: userAchievements.publicKey,
         authority: provider.wallet.publicKey,
         tokenMint: tokenMint.publicKey,
         // userTokenAccount: userTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
       })
-      .signers([userAchievement])
       .rpc();
     log("Fungible achievement awarded");
   
@@ -156,6 +158,27 @@ describe("Achievement Tests", () => {
     log("Updated user achievements:", updatedUserAchievements);
     expect(updatedUserAchievements.achievements).to.have.lengthOf(1);
     expect(updatedUserAchievements.achievements[0].toString()).to.equal(achievement.publicKey.toString());
+
+    const userAchievementAccount = await program.account.userAchievement.fetch(findUserAchievementPda(achievement.publicKey, user.publicKey));
+    expect(userAchievementAccount.brand.toString()).to.equal(brandPda.toString());
+
+    try {
+      await program.methods
+        .awardFungibleAchievement()
+        .accounts({
+          brand: brandPda,
+          achievement: achievement.publicKey,
+          user: user.publicKey,
+          userAchievements: userAchievements.publicKey,
+          authority: provider.wallet.publicKey,
+          tokenMint: tokenMint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: AchievementAlreadyAwarded");
+    }
     
     log("Fungible achievement test completed successfully");
   });
@@ -178,7 +201,8 @@ describe("Achievement Tests", () => {
           "A test non-fungible achievement",
           "Complete the special test",
           1000,
-          "https://example.com/metadata.json"
+          "https://example.com/metadata.json",
+          false
         )
         .accounts({
           brand: brandPda,
@@ -230,13 +254,16 @@ describe("Achievement Tests", () => {
           "A test fungible achievement",
           "Complete the test",
           100,
-          new anchor.BN(1000000)
+          new anchor.BN(1000000),
+          false,
+          null
         )
         .accounts({
           brand: brandPda,
           achievement: achievement.publicKey,
           tokenMint: tokenMint.publicKey,
           tronicAdmin: nonAdminKeypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([achievement, tokenMint, nonAdminKeypair])
         .rpc();
@@ -266,7 +293,8 @@ describe("Achievement Tests", () => {
           "A test non-fungible achievement",
           "Complete the special test",
           1000,
-          "https://example.com/metadata.json"
+          "https://example.com/metadata.json",
+          false
         )
         .accounts({
           brand: brandPda,
@@ -282,4 +310,922 @@ describe("Achievement Tests", () => {
       expect(error.message).to.include("Error Code: UnauthorizedTronicAdmin");
     }
   });
-});
\ No newline at end of file
+
+  it("Closes an achievement and unlinks it from the brand", async () => {
+    const brandPda = await createUniqueBrand();
+    const achievement = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .createAchievement("Retired Achievement", "To be closed", "None", 10)
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        authority: provider.wallet.publicKey,
+      })
+      .signers([achievement])
+      .rpc();
+
+    await program.methods
+      .closeAchievement()
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        authority: TRONIC_ADMIN_PUBKEY,
+        recipient: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(achievement.publicKey)).to.be.null;
+    const brandAccount = await program.account.brand.fetch(brandPda);
+    expect(brandAccount.achievements.some(a => a.equals(achievement.publicKey))).to.be.false;
+  });
+
+  it("Updates and archives an achievement", async () => {
+    const brandPda = await createUniqueBrand();
+    const achievement = anchor.web3.Keypair.generate();
+
+    // Criteria is bounded at creation as well as on update
+    try {
+      await program.methods
+        .createAchievement("Original Achievement", "Original description", "x".repeat(201), 10)
+        .accounts({
+          brand: brandPda,
+          achievement: achievement.publicKey,
+          authority: provider.wallet.publicKey,
+        })
+        .signers([achievement])
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: CriteriaTooLong");
+    }
+
+    await program.methods
+      .createAchievement("Original Achievement", "Original description", "Original criteria", 10)
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        authority: provider.wallet.publicKey,
+      })
+      .signers([achievement])
+      .rpc();
+
+    const updateAccounts = {
+      brand: brandPda,
+      achievement: achievement.publicKey,
+      authority: TRONIC_ADMIN_PUBKEY,
+    };
+
+    try {
+      await program.methods
+        .updateAchievement("Updated Achievement", "Updated description", "Updated criteria", 20, "https://example.com/a.json")
+        .accounts(updateAccounts)
+        .signers([TRONIC_ADMIN_KEYPAIR])
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: InvalidAchievementType");
+    }
+
+    try {
+      await program.methods
+        .updateAchievement("Updated Achievement", "Updated description", "x".repeat(201), 20, null)
+        .accounts(updateAccounts)
+        .signers([TRONIC_ADMIN_KEYPAIR])
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: CriteriaTooLong");
+    }
+
+    await program.methods
+      .updateAchievement("Updated Achievement", "Updated description", "Updated criteria", 20, null)
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        authority: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .archiveAchievement()
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        authority: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    const achievementInfo = await program.methods
+      .getAchievementInfo()
+      .accounts({
+        achievement: achievement.publicKey,
+      })
+      .view();
+
+    expect(achievementInfo.name).to.equal("Updated Achievement");
+    expect(achievementInfo.description).to.equal("Updated description");
+    expect(achievementInfo.criteria).to.equal("Updated criteria");
+    expect(achievementInfo.points).to.equal(20);
+    expect(achievementInfo.archived).to.be.true;
+  });
+
+  it("Freezes soulbound achievements and lets them be thawed and burned", async () => {
+    const brandPda = await createUniqueBrand();
+    const achievement = anchor.web3.Keypair.generate();
+    const tokenMint = anchor.web3.Keypair.generate();
+    const user = anchor.web3.Keypair.generate();
+    const userAchievements = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .createFungibleAchievement("Soulbound Badge", "A non-transferable badge", "Show up", 10, new anchor.BN(1000), true, null)
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([achievement, tokenMint, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .initializeUserAchievements()
+      .accounts({
+        userAchievements: userAchievements.publicKey,
+        user: user.publicKey,
+        authority: provider.wallet.publicKey,
+      })
+      .signers([userAchievements, user])
+      .rpc();
+
+    await program.methods
+      .awardFungibleAchievement()
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        user: user.publicKey,
+        userAchievements: userAchievements.publicKey,
+        authority: provider.wallet.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const userTokenAccount = await anchor.utils.token.associatedAddress({
+      mint: tokenMint.publicKey,
+      owner: user.publicKey
+    });
+    expect((await getAccount(provider.connection, userTokenAccount)).isFrozen).to.be.true;
+
+    // SPL Token badges have no delegate, so only their holder can burn them
+    try {
+      await program.methods
+        .revokeSoulbound(new anchor.BN(1))
+        .accounts({
+          brand: brandPda,
+          mint: tokenMint.publicKey,
+          tokenAccount: userTokenAccount,
+          authority: TRONIC_ADMIN_PUBKEY,
+          edition: null,
+          tokenMetadataProgram: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([TRONIC_ADMIN_KEYPAIR])
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: SoulboundNotRevocable");
+    }
+
+    // Nothing is re-frozen after the burn, so it has to take the whole balance
+    try {
+      await program.methods
+        .thawAndBurn(new anchor.BN(0))
+        .accounts({
+          brand: brandPda,
+          mint: tokenMint.publicKey,
+          tokenAccount: userTokenAccount,
+          holder: user.publicKey,
+          freezeAuthority: null,
+          edition: null,
+          tokenMetadataProgram: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: SoulboundPartialBurn");
+    }
+
+    await program.methods
+      .thawAndBurn(new anchor.BN(1))
+      .accounts({
+        brand: brandPda,
+        mint: tokenMint.publicKey,
+        tokenAccount: userTokenAccount,
+        holder: user.publicKey,
+        freezeAuthority: null,
+        edition: null,
+        tokenMetadataProgram: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const burnedAccount = await getAccount(provider.connection, userTokenAccount);
+    expect(burnedAccount.isFrozen).to.be.false;
+    expect(Number(burnedAccount.amount)).to.equal(0);
+  });
+
+  it("Creates a non-transferable Token-2022 badge with on-mint metadata", async () => {
+    const brandPda = await createUniqueBrand();
+    const achievement = anchor.web3.Keypair.generate();
+    const tokenMint = anchor.web3.Keypair.generate();
+    const user = anchor.web3.Keypair.generate();
+    const userAchievements = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .createFungibleAchievement(
+        "2022 Badge",
+        "A Token-2022 badge",
+        "Show up",
+        10,
+        new anchor.BN(1000),
+        true,
+        { symbol: "BDG", uri: "https://example.com/badge.json" }
+      )
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([achievement, tokenMint, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    const mint = await getMint(provider.connection, tokenMint.publicKey, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(getExtensionTypes(mint.tlvData)).to.include.members([
+      ExtensionType.NonTransferable,
+      ExtensionType.MetadataPointer,
+    ]);
+    const metadata = await getTokenMetadata(provider.connection, tokenMint.publicKey);
+    expect(metadata.name).to.equal("2022 Badge");
+    expect(metadata.symbol).to.equal("BDG");
+    expect(metadata.uri).to.equal("https://example.com/badge.json");
+
+    await program.methods
+      .initializeUserAchievements()
+      .accounts({
+        userAchievements: userAchievements.publicKey,
+        user: user.publicKey,
+        authority: provider.wallet.publicKey,
+      })
+      .signers([userAchievements, user])
+      .rpc();
+
+    await program.methods
+      .awardFungibleAchievement()
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        user: user.publicKey,
+        userAchievements: userAchievements.publicKey,
+        authority: provider.wallet.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    // Non-transferable badges don't need to be frozen
+    const userTokenAccount = anchor.web3.PublicKey.findProgramAddressSync(
+      [user.publicKey.toBuffer(), TOKEN_2022_PROGRAM_ID.toBuffer(), tokenMint.publicKey.toBuffer()],
+      ASSOCIATED_TOKEN_PROGRAM_ID
+    )[0];
+    const account = await getAccount(provider.connection, userTokenAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(Number(account.amount)).to.equal(1);
+    expect(account.isFrozen).to.be.false;
+  });
+
+  it("Rejects Token-2022 extensions on an SPL Token mint", async () => {
+    const brandPda = await createUniqueBrand();
+    const achievement = anchor.web3.Keypair.generate();
+    const tokenMint = anchor.web3.Keypair.generate();
+
+    try {
+      await program.methods
+        .createFungibleAchievement("Badge", "A badge", "Show up", 10, new anchor.BN(1000), false, { symbol: "BDG", uri: "" })
+        .accounts({
+          brand: brandPda,
+          achievement: achievement.publicKey,
+          tokenMint: tokenMint.publicKey,
+          tronicAdmin: TRONIC_ADMIN_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([achievement, tokenMint, TRONIC_ADMIN_KEYPAIR])
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: Token2022Required");
+    }
+  });
+
+  it("Awards compressed achievements from a program-owned Merkle tree", async () => {
+    const brandPda = await createUniqueBrand();
+    const achievement = anchor.web3.Keypair.generate();
+    const merkleTree = anchor.web3.Keypair.generate();
+    const user = anchor.web3.Keypair.generate();
+    const userAchievements = anchor.web3.Keypair.generate();
+
+    // Concurrent Merkle tree account size without a canopy:
+    // header + sequence/index/buffer size + changelog buffer + rightmost proof
+    const maxDepth = 3;
+    const maxBufferSize = 8;
+    const changeLogSize = 32 + 32 * maxDepth + 4 + 4;
+    const treeSpace = 2 + 54 + 24 + maxBufferSize * changeLogSize + (32 * maxDepth + 32 + 4 + 4);
+    const allocTree = anchor.web3.SystemProgram.createAccount({
+      fromPubkey: TRONIC_ADMIN_PUBKEY,
+      newAccountPubkey: merkleTree.publicKey,
+      lamports: await provider.connection.getMinimumBalanceForRentExemption(treeSpace),
+      space: treeSpace,
+      programId: ACCOUNT_COMPRESSION_PROGRAM_ID,
+    });
+
+    await program.methods
+      .createCompressedAchievement(
+        "Compressed Badge",
+        "A mass-campaign badge",
+        "Attend the event",
+        5,
+        "https://example.com/cnft.json",
+        maxDepth,
+        maxBufferSize
+      )
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        merkleTree: merkleTree.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+      })
+      .preInstructions([allocTree])
+      .signers([achievement, merkleTree, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    const [treeConfig] = anchor.web3.PublicKey.findProgramAddressSync(
+      [merkleTree.publicKey.toBuffer()],
+      BUBBLEGUM_PROGRAM_ID
+    );
+    const treeConfigInfo = await provider.connection.getAccountInfo(treeConfig);
+    expect(treeConfigInfo.owner.toString()).to.equal(BUBBLEGUM_PROGRAM_ID.toString());
+
+    await program.methods
+      .initializeUserAchievements()
+      .accounts({
+        userAchievements: userAchievements.publicKey,
+        user: user.publicKey,
+        authority: provider.wallet.publicKey,
+      })
+      .signers([userAchievements, user])
+      .rpc();
+
+    await program.methods
+      .awardCompressedAchievement()
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        user: user.publicKey,
+        userAchievements: userAchievements.publicKey,
+        authority: provider.wallet.publicKey,
+        merkleTree: merkleTree.publicKey,
+      })
+      .rpc();
+
+    const achievementAccount = await program.account.achievement.fetch(achievement.publicKey);
+    expect(achievementAccount.achievementType).to.deep.equal({ compressed: {} });
+    expect(achievementAccount.merkleTree.toString()).to.equal(merkleTree.publicKey.toString());
+    expect(achievementAccount.tokenSupply.toNumber()).to.equal(1);
+
+    const userAchievementAccount = await program.account.userAchievement.fetch(findUserAchievementPda(achievement.publicKey, user.publicKey));
+    expect(userAchievementAccount.user.toString()).to.equal(user.publicKey.toString());
+    expect(userAchievementAccount.achievement.toString()).to.equal(achievement.publicKey.toString());
+  });
+
+  it("Batch awards a fungible achievement and skips users already awarded", async () => {
+    const brandPda = await createUniqueBrand();
+    const achievement = anchor.web3.Keypair.generate();
+    const tokenMint = anchor.web3.Keypair.generate();
+    const users = [anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate()];
+    const userAchievementsAccounts = [anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate()];
+
+    await program.methods
+      .createFungibleAchievement("Check-in", "Event check-in badge", "Attend", 1, new anchor.BN(1000), false, null)
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([achievement, tokenMint, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    for (let i = 0; i < users.length; i++) {
+      await program.methods
+        .initializeUserAchievements()
+        .accounts({
+          userAchievements: userAchievementsAccounts[i].publicKey,
+          user: users[i].publicKey,
+          authority: provider.wallet.publicKey,
+        })
+        .signers([userAchievementsAccounts[i], users[i]])
+        .rpc();
+    }
+
+    const userPointsFor = (i: number) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("user-points"), brandPda.toBuffer(), users[i].publicKey.toBuffer()],
+        program.programId
+      )[0];
+    const tupleFor = (i: number) => {
+      const [userAchievement] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("user-achievement"), achievement.publicKey.toBuffer(), users[i].publicKey.toBuffer()],
+        program.programId
+      );
+      const userTokenAccount = anchor.utils.token.associatedAddress({
+        mint: tokenMint.publicKey,
+        owner: users[i].publicKey,
+      });
+      return [
+        { pubkey: users[i].publicKey, isSigner: false, isWritable: false },
+        { pubkey: userAchievementsAccounts[i].publicKey, isSigner: false, isWritable: true },
+        { pubkey: userTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: userAchievement, isSigner: false, isWritable: true },
+        { pubkey: userPointsFor(i), isSigner: false, isWritable: true },
+      ];
+    };
+
+    const batchAward = (tuples: anchor.web3.AccountMeta[]) =>
+      program.methods
+        .batchAwardFungibleAchievement()
+        .accounts({
+          brand: brandPda,
+          achievement: achievement.publicKey,
+          authority: provider.wallet.publicKey,
+          tokenMint: tokenMint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(tuples);
+
+    await batchAward(tupleFor(0)).rpc();
+
+    // Lamports sent to an unused record address don't block its creation
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: tupleFor(1)[3].pubkey,
+          lamports: 1_000_000,
+        })
+      )
+    );
+
+    // The first user was already awarded, so only the second one is minted
+    await batchAward([...tupleFor(0), ...tupleFor(1)]).rpc();
+
+    for (const user of users) {
+      const userTokenAccount = anchor.utils.token.associatedAddress({ mint: tokenMint.publicKey, owner: user.publicKey });
+      const balance = await provider.connection.getTokenAccountBalance(userTokenAccount);
+      expect(balance.value.uiAmount).to.equal(1);
+    }
+    const userAchievements = await program.account.userAchievements.fetch(userAchievementsAccounts[0].publicKey);
+    expect(userAchievements.achievements).to.have.lengthOf(1);
+
+    try {
+      await batchAward(tupleFor(1).slice(0, 3)).rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: InvalidBatchAccounts");
+    }
+  });
+
+  it("Limits delegated attesters to their achievements and daily quota", async () => {
+    const brandPda = await createUniqueBrand();
+    const achievement = anchor.web3.Keypair.generate();
+    const tokenMint = anchor.web3.Keypair.generate();
+    const attester = anchor.web3.Keypair.generate();
+    const stranger = anchor.web3.Keypair.generate();
+    await fundAccount(provider.connection, attester.publicKey);
+    await fundAccount(provider.connection, stranger.publicKey);
+
+    await program.methods
+      .createFungibleAchievement("Volunteer", "Helped at an event", "Volunteer once", 5, new anchor.BN(1000), false, null)
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([achievement, tokenMint, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    const [attesterPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("attester"), brandPda.toBuffer(), attester.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .createAttester(attester.publicKey, [achievement.publicKey], false, 1, null)
+      .accounts({
+        brand: brandPda,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    const award = async (authority: anchor.web3.Keypair, attesterAccount: anchor.web3.PublicKey | null) => {
+      const user = anchor.web3.Keypair.generate();
+      const userAchievements = anchor.web3.Keypair.generate();
+      await program.methods
+        .initializeUserAchievements()
+        .accounts({
+          userAchievements: userAchievements.publicKey,
+          user: user.publicKey,
+          authority: provider.wallet.publicKey,
+        })
+        .signers([userAchievements, user])
+        .rpc();
+
+      await program.methods
+        .awardFungibleAchievement()
+        .accounts({
+          brand: brandPda,
+          achievement: achievement.publicKey,
+          user: user.publicKey,
+          userAchievements: userAchievements.publicKey,
+          authority: authority.publicKey,
+          attester: attesterAccount,
+          tokenMint: tokenMint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([authority])
+        .rpc();
+    };
+
+    try {
+      await award(stranger, null);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: Unauthorized");
+    }
+
+    await award(attester, attesterPda);
+    const attesterAccount = await program.account.attester.fetch(attesterPda);
+    expect(attesterAccount.awardedToday).to.equal(1);
+
+    try {
+      await award(attester, attesterPda);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: AttesterQuotaExceeded");
+    }
+  });
+
+  it("Records progress and auto-awards once threshold rules are met", async () => {
+    const brandPda = await createUniqueBrand();
+    const achievement = anchor.web3.Keypair.generate();
+    const tokenMint = anchor.web3.Keypair.generate();
+    const user = anchor.web3.Keypair.generate();
+    const userAchievements = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .createFungibleAchievement("Regular", "Checked in three times", "checkins >= 3", 10, new anchor.BN(1000), false, null)
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([achievement, tokenMint, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .setAchievementRules([{ metric: "checkins", threshold: new anchor.BN(3) }])
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    await program.methods
+      .initializeUserAchievements()
+      .accounts({
+        userAchievements: userAchievements.publicKey,
+        user: user.publicKey,
+        authority: provider.wallet.publicKey,
+      })
+      .signers([userAchievements, user])
+      .rpc();
+
+    const userTokenAccount = anchor.utils.token.associatedAddress({
+      mint: tokenMint.publicKey,
+      owner: user.publicKey,
+    });
+    const recordProgress = (metric: string, delta: number) =>
+      program.methods
+        .recordProgress(metric, new anchor.BN(delta))
+        .accounts({
+          brand: brandPda,
+          achievement: achievement.publicKey,
+          user: user.publicKey,
+          userAchievements: userAchievements.publicKey,
+          authority: provider.wallet.publicKey,
+          tokenMint: tokenMint.publicKey,
+          userTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+    await recordProgress("checkins", 2);
+    expect(await provider.connection.getAccountInfo(userTokenAccount)).to.be.null;
+
+    await recordProgress("checkins", 1);
+    const balance = await provider.connection.getTokenAccountBalance(userTokenAccount);
+    expect(balance.value.uiAmount).to.equal(1);
+
+    // Further progress keeps counting without awarding again
+    await recordProgress("checkins", 1);
+    const [userProgress] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user-progress"), achievement.publicKey.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    const progress = await program.account.userProgress.fetch(userProgress);
+    expect(progress.counters[0].value.toNumber()).to.equal(4);
+    const updatedUserAchievements = await program.account.userAchievements.fetch(userAchievements.publicKey);
+    expect(updatedUserAchievements.achievements).to.have.lengthOf(1);
+
+    try {
+      await recordProgress("purchases", 1);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: UnknownMetric");
+    }
+  });
+
+  it("Requires prerequisite achievements before awarding the next tier", async () => {
+    const brandPda = await createUniqueBrand();
+    const user = anchor.web3.Keypair.generate();
+    const userAchievements = anchor.web3.Keypair.generate();
+
+    const createTier = async (name: string) => {
+      const achievement = anchor.web3.Keypair.generate();
+      const tokenMint = anchor.web3.Keypair.generate();
+      await program.methods
+        .createFungibleAchievement(name, `${name} tier badge`, "Be a fan", 10, new anchor.BN(1000), false, null)
+        .accounts({
+          brand: brandPda,
+          achievement: achievement.publicKey,
+          tokenMint: tokenMint.publicKey,
+          tronicAdmin: TRONIC_ADMIN_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([achievement, tokenMint, TRONIC_ADMIN_KEYPAIR])
+        .rpc();
+      return { achievement: achievement.publicKey, tokenMint: tokenMint.publicKey };
+    };
+    const bronze = await createTier("Bronze Fan");
+    const silver = await createTier("Silver Fan");
+
+    await program.methods
+      .setAchievementPrerequisites([bronze.achievement])
+      .accounts({
+        brand: brandPda,
+        achievement: silver.achievement,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    await program.methods
+      .initializeUserAchievements()
+      .accounts({
+        userAchievements: userAchievements.publicKey,
+        user: user.publicKey,
+        authority: provider.wallet.publicKey,
+      })
+      .signers([userAchievements, user])
+      .rpc();
+
+    const award = async (tier: { achievement: anchor.web3.PublicKey, tokenMint: anchor.web3.PublicKey }, records: anchor.web3.PublicKey[]) => {
+      await program.methods
+        .awardFungibleAchievement()
+        .accounts({
+          brand: brandPda,
+          achievement: tier.achievement,
+          user: user.publicKey,
+          userAchievements: userAchievements.publicKey,
+          authority: provider.wallet.publicKey,
+          tokenMint: tier.tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(records.map(pubkey => ({ pubkey, isSigner: false, isWritable: false })))
+        .rpc();
+      return findUserAchievementPda(tier.achievement, user.publicKey);
+    };
+
+    try {
+      await award(silver, []);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: PrerequisiteMissing");
+    }
+
+    const bronzeRecord = await award(bronze, []);
+    await award(silver, [bronzeRecord]);
+
+    const updatedUserAchievements = await program.account.userAchievements.fetch(userAchievements.publicKey);
+    expect(updatedUserAchievements.achievements.map(a => a.toBase58())).to.deep.equal([
+      bronze.achievement.toBase58(),
+      silver.achievement.toBase58(),
+    ]);
+  });
+
+  it("Enforces the availability window and award cap", async () => {
+    const brandPda = await createUniqueBrand();
+    const achievement = anchor.web3.Keypair.generate();
+    const tokenMint = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .createFungibleAchievement("Launch Day", "Was there on launch day", "Attend the launch", 20, new anchor.BN(1000), false, null)
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([achievement, tokenMint, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    const setAvailability = (from: number | null, until: number | null, maxAwards: number | null) =>
+      program.methods
+        .setAchievementAvailability(
+          from === null ? null : new anchor.BN(from),
+          until === null ? null : new anchor.BN(until),
+          maxAwards === null ? null : new anchor.BN(maxAwards)
+        )
+        .accounts({
+          brand: brandPda,
+          achievement: achievement.publicKey,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+
+    const award = async () => {
+      const user = anchor.web3.Keypair.generate();
+      const userAchievements = anchor.web3.Keypair.generate();
+      await program.methods
+        .initializeUserAchievements()
+        .accounts({
+          userAchievements: userAchievements.publicKey,
+          user: user.publicKey,
+          authority: provider.wallet.publicKey,
+        })
+        .signers([userAchievements, user])
+        .rpc();
+
+      await program.methods
+        .awardFungibleAchievement()
+        .accounts({
+          brand: brandPda,
+          achievement: achievement.publicKey,
+          user: user.publicKey,
+          userAchievements: userAchievements.publicKey,
+          authority: provider.wallet.publicKey,
+          tokenMint: tokenMint.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+    };
+    const expectError = async (code: string) => {
+      try {
+        await award();
+        expect.fail("Expected an error but none was thrown");
+      } catch (error) {
+        expect(error.message).to.include(`Error Code: ${code}`);
+      }
+    };
+
+    const now = Math.floor(Date.now() / 1000);
+    await setAvailability(now + 3600, null, null);
+    await expectError("AchievementNotYetAvailable");
+
+    await setAvailability(null, now - 3600, null);
+    await expectError("AchievementClosed");
+
+    await setAvailability(null, null, 1);
+    await award();
+    await expectError("AchievementSoldOut");
+
+    const info = await program.methods
+      .getAchievementInfo()
+      .accounts({ achievement: achievement.publicKey })
+      .view();
+    expect(info.awardedCount.toNumber()).to.equal(1);
+    expect(info.maxAwards.toNumber()).to.equal(1);
+  });
+
+  it("Only awards a fungible achievement with its own mint", async () => {
+    const createBadge = async (brandPda: anchor.web3.PublicKey) => {
+      const achievement = anchor.web3.Keypair.generate();
+      const tokenMint = anchor.web3.Keypair.generate();
+      await program.methods
+        .createFungibleAchievement("Badge", "A brand badge", "Show up", 10, new anchor.BN(1000), false, null)
+        .accounts({
+          brand: brandPda,
+          achievement: achievement.publicKey,
+          tokenMint: tokenMint.publicKey,
+          tronicAdmin: TRONIC_ADMIN_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([achievement, tokenMint, TRONIC_ADMIN_KEYPAIR])
+        .rpc();
+      return { achievement: achievement.publicKey, tokenMint: tokenMint.publicKey };
+    };
+
+    const brandPda = await createUniqueBrand();
+    const otherBrandPda = await createUniqueBrand();
+    const badge = await createBadge(brandPda);
+    const otherBadge = await createBadge(otherBrandPda);
+
+    const user = anchor.web3.Keypair.generate();
+    const userAchievements = anchor.web3.Keypair.generate();
+    await program.methods
+      .initializeUserAchievements()
+      .accounts({
+        userAchievements: userAchievements.publicKey,
+        user: user.publicKey,
+        authority: provider.wallet.publicKey,
+      })
+      .signers([userAchievements, user])
+      .rpc();
+
+    try {
+      await program.methods
+        .awardFungibleAchievement()
+        .accounts({
+          brand: brandPda,
+          achievement: badge.achievement,
+          user: user.publicKey,
+          userAchievements: userAchievements.publicKey,
+          authority: provider.wallet.publicKey,
+          tokenMint: otherBadge.tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: InvalidAchievementType");
+    }
+
+    // Each brand's badges are minted by its own authority
+    const mintAuthorityOf = (brand: anchor.web3.PublicKey) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("mint-authority"), brand.toBuffer()],
+        program.programId
+      )[0];
+    const mint = await getMint(provider.connection, badge.tokenMint);
+    const otherMint = await getMint(provider.connection, otherBadge.tokenMint);
+    expect(mint.mintAuthority.toString()).to.equal(mintAuthorityOf(brandPda).toString());
+    expect(otherMint.mintAuthority.toString()).to.equal(mintAuthorityOf(otherBrandPda).toString());
+  });
+
+  it("Only lets brand authorities create achievements", async () => {
+    const brandPda = await createUniqueBrand();
+    const stranger = anchor.web3.Keypair.generate();
+    await fundAccount(provider.connection, stranger.publicKey);
+
+    const achievement = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .createAchievement("Spam", "Not the brand's achievement", "None", 10)
+        .accounts({
+          brand: brandPda,
+          achievement: achievement.publicKey,
+          authority: stranger.publicKey,
+        })
+        .signers([achievement, stranger])
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: Unauthorized");
+    }
+
+    const brand = await program.account.brand.fetch(brandPda);
+    expect(brand.achievements).to.have.lengthOf(0);
+  });
+});
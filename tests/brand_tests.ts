@@ -8,6 +8,9 @@ import {
   createUniqueBrand, 
   initializeProgramState,
   fundAccount,
+  findBrandPda,
+  findBrandNameRegistryPda,
+  uniqueBrandId,
   log, 
   TRONIC_ADMIN_KEYPAIR, 
   TRONIC_ADMIN_PUBKEY 
@@ -21,10 +24,8 @@ describe("Brand Tests", () => {
     const name = "Test Brand";
     const description = "A test brand for our community engagement protocol";
   
-    const [brandPda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("brand"), Buffer.from(name)],
-      program.programId
-    );
+    const brandId = uniqueBrandId();
+    const brandPda = findBrandPda(brandId);
   
     const [brandListPda] = anchor.web3.PublicKey.findProgramAddressSync(
       [Buffer.from("brand-list")],
@@ -36,8 +37,9 @@ describe("Brand Tests", () => {
   
     try {
       const tx = await program.methods
-        .createBrand(name, description, null, null, null, [])
+        .createBrand(brandId, name, description, null, null, null, [])
         .accounts({
+          nameRegistry: findBrandNameRegistryPda(name),
           tronicAdmin: TRONIC_ADMIN_PUBKEY,
         })
         .signers([TRONIC_ADMIN_KEYPAIR])
@@ -74,10 +76,8 @@ describe("Brand Tests", () => {
     const category = "Technology";
     const tags = ["test", "community", "engagement"];
 
-    const [brandPda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("brand"), Buffer.from(name)],
-      program.programId
-    );
+    const brandId = uniqueBrandId();
+    const brandPda = findBrandPda(brandId);
 
     const [brandListPda] = anchor.web3.PublicKey.findProgramAddressSync(
       [Buffer.from("brand_list")],
@@ -85,8 +85,9 @@ describe("Brand Tests", () => {
     );
 
     await program.methods
-      .createBrand(name, description, website, socialMedia, category, tags)
+      .createBrand(brandId, name, description, website, socialMedia, category, tags)
       .accounts({
+        nameRegistry: findBrandNameRegistryPda(name),
         // brandList: brandListPda,
         tronicAdmin: TRONIC_ADMIN_PUBKEY,
       })
@@ -114,6 +115,7 @@ describe("Brand Tests", () => {
     const brandPda = await createUniqueBrand();
     log("Created Brand for update test with publicKey:", brandPda);
 
+    const oldName = (await program.account.brand.fetch(brandPda)).name;
     const newName = "Updated Test Brand";
     const newDescription = "An updated test brand for our community engagement protocol";
   
@@ -121,6 +123,8 @@ describe("Brand Tests", () => {
       .updateBrand(newName, newDescription)
       .accounts({
         brand: brandPda,
+        currentNameRegistry: findBrandNameRegistryPda(oldName),
+        newNameRegistry: findBrandNameRegistryPda(newName),
       })
       .signers([TRONIC_ADMIN_KEYPAIR])
       .rpc();
@@ -131,20 +135,52 @@ describe("Brand Tests", () => {
     log("Updated Brand Account:", updatedBrandAccount);
     expect(updatedBrandAccount.name).to.equal(newName);
     expect(updatedBrandAccount.description).to.equal(newDescription);
+
+    // The new name resolves to the brand and the old name is released
+    const registry = await program.account.brandNameRegistry.fetch(findBrandNameRegistryPda(newName));
+    expect(registry.brand.toString()).to.equal(brandPda.toString());
+    const oldRegistry = await provider.connection.getAccountInfo(findBrandNameRegistryPda(oldName));
+    expect(oldRegistry).to.be.null;
+  });
+
+  it("Fails to rename a brand to a name held by another brand", async () => {
+    const brandPda = await createUniqueBrand();
+    const otherBrandPda = await createUniqueBrand();
+    const name = (await program.account.brand.fetch(brandPda)).name;
+    const takenName = (await program.account.brand.fetch(otherBrandPda)).name;
+
+    try {
+      await program.methods
+        .updateBrand(takenName, "Renamed description")
+        .accounts({
+          brand: brandPda,
+          currentNameRegistry: findBrandNameRegistryPda(name),
+          newNameRegistry: findBrandNameRegistryPda(takenName),
+        })
+        .signers([TRONIC_ADMIN_KEYPAIR])
+        .rpc();
+
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      log("Error caught:", error.message);
+      expect(error.message).to.include("Error Code: BrandNameTaken");
+    }
+
+    const brandAccount = await program.account.brand.fetch(brandPda);
+    expect(brandAccount.name).to.equal(name);
   });
 
   it("Gets brand info", async () => {
     const name = "Test Brand 2";
     const description = "A test brand for our community engagement protocol";
   
-    const [brandPda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("brand"), Buffer.from(name)],
-      program.programId
-    );
+    const brandId = uniqueBrandId();
+    const brandPda = findBrandPda(brandId);
 
     await program.methods
-      .createBrand(name, description, null, null, null, [])
+      .createBrand(brandId, name, description, null, null, null, [])
       .accounts({
+        nameRegistry: findBrandNameRegistryPda(name),
         // brandList: brandListPda,
         tronicAdmin: TRONIC_ADMIN_PUBKEY,
       })
@@ -204,6 +240,7 @@ describe("Brand Tests", () => {
   it("Fails to create a brand with non-admin signer", async () => {
     const name = "Test Brand 4";
     const description = "A test brand for our community engagement protocol";
+    const brandId = uniqueBrandId();
     const nonAdminKeypair = anchor.web3.Keypair.generate();
 
     // Fund the non-admin account
@@ -230,8 +267,9 @@ describe("Brand Tests", () => {
 
     try {
       const tx = await program.methods
-        .createBrand(name, description, null, null, null, [])
+        .createBrand(brandId, name, description, null, null, null, [])
         .accounts({
+          nameRegistry: findBrandNameRegistryPda(name),
           // brandList: brandListPda,
           tronicAdmin: nonAdminKeypair.publicKey,
         })
@@ -264,10 +302,8 @@ describe("Brand Tests", () => {
   it("Fails to update a brand with non-admin signer", async () => {
     // First, create a brand with the Tronic Admin
     const brandName = "Original Brand 1";
-    const [brandPda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("brand"), Buffer.from(brandName)],
-      program.programId
-    );
+    const brandId = uniqueBrandId();
+    const brandPda = findBrandPda(brandId);
 
     const [brandListPda] = anchor.web3.PublicKey.findProgramAddressSync(
       [Buffer.from("brand_list")],
@@ -275,8 +311,9 @@ describe("Brand Tests", () => {
     );
   
     await program.methods
-      .createBrand(brandName, "Original description", null, null, null, [])
+      .createBrand(brandId, brandName, "Original description", null, null, null, [])
       .accounts({
+        nameRegistry: findBrandNameRegistryPda(brandName),
         // brandList: brandListPda,
         tronicAdmin: TRONIC_ADMIN_PUBKEY,
       })
@@ -294,6 +331,8 @@ describe("Brand Tests", () => {
         .updateBrand("Updated Brand", "Updated description")
         .accounts({
           brand: brandPda,
+          currentNameRegistry: findBrandNameRegistryPda(brandName),
+          newNameRegistry: findBrandNameRegistryPda("Updated Brand"),
           tronicAdmin: nonAdminKeypair.publicKey,
         })
         .signers([nonAdminKeypair])
@@ -310,4 +349,136 @@ describe("Brand Tests", () => {
     expect(brandAccount.name).to.equal(brandName);
     expect(brandAccount.description).to.equal("Original description");
   });
-});
\ No newline at end of file
+
+  it("Transfers brand ownership in two steps", async () => {
+    const brandPda = await createUniqueBrand();
+    const newOwner = anchor.web3.Keypair.generate();
+    const outsider = anchor.web3.Keypair.generate();
+
+    let brandAccount = await program.account.brand.fetch(brandPda);
+    expect(brandAccount.owner.toString()).to.equal(TRONIC_ADMIN_PUBKEY.toString());
+
+    await program.methods
+      .transferBrandOwnership(newOwner.publicKey)
+      .accounts({
+        brand: brandPda,
+        owner: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    brandAccount = await program.account.brand.fetch(brandPda);
+    expect(brandAccount.owner.toString()).to.equal(TRONIC_ADMIN_PUBKEY.toString());
+    expect(brandAccount.pendingOwner.toString()).to.equal(newOwner.publicKey.toString());
+
+    try {
+      await program.methods
+        .acceptBrandOwnership()
+        .accounts({
+          brand: brandPda,
+          newOwner: outsider.publicKey,
+        })
+        .signers([outsider])
+        .rpc();
+
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      log("Error caught:", error.message);
+      expect(error.message).to.include("Error Code: Unauthorized");
+    }
+
+    await program.methods
+      .acceptBrandOwnership()
+      .accounts({
+        brand: brandPda,
+        newOwner: newOwner.publicKey,
+      })
+      .signers([newOwner])
+      .rpc();
+
+    brandAccount = await program.account.brand.fetch(brandPda);
+    expect(brandAccount.owner.toString()).to.equal(newOwner.publicKey.toString());
+    expect(brandAccount.pendingOwner).to.be.null;
+  });
+
+  it("Closes an empty brand and reclaims its rent", async () => {
+    const brandPda = await createUniqueBrand();
+    const name = (await program.account.brand.fetch(brandPda)).name;
+    const recipient = anchor.web3.Keypair.generate();
+
+    const [brandListPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("brand-list")],
+      program.programId
+    );
+
+    await program.methods
+      .closeBrand()
+      .accounts({
+        brand: brandPda,
+        nameRegistry: findBrandNameRegistryPda(name),
+        authority: TRONIC_ADMIN_PUBKEY,
+        recipient: recipient.publicKey,
+      })
+      .signers([TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(brandPda)).to.be.null;
+    expect(await provider.connection.getAccountInfo(findBrandNameRegistryPda(name))).to.be.null;
+    expect(await provider.connection.getBalance(recipient.publicKey)).to.be.greaterThan(0);
+
+    const brandListAccount = await program.account.brandList.fetch(brandListPda);
+    expect(brandListAccount.brands.some(b => b.equals(brandPda))).to.be.false;
+  });
+
+  it("Fails to close a brand that still has achievements", async () => {
+    const brandPda = await createUniqueBrand();
+    const name = (await program.account.brand.fetch(brandPda)).name;
+    const achievement = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .createAchievement("Achievement", "Description", "Criteria", 10)
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        authority: provider.wallet.publicKey,
+      })
+      .signers([achievement])
+      .rpc();
+
+    try {
+      await program.methods
+        .closeBrand()
+        .accounts({
+          brand: brandPda,
+          nameRegistry: findBrandNameRegistryPda(name),
+          authority: TRONIC_ADMIN_PUBKEY,
+          recipient: TRONIC_ADMIN_PUBKEY,
+        })
+        .signers([TRONIC_ADMIN_KEYPAIR])
+        .rpc();
+
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      log("Error caught:", error.message);
+      expect(error.message).to.include("Error Code: BrandNotEmpty");
+    }
+  });
+
+  it("Fails to create a brand with a name held by another brand", async () => {
+    const takenName = (await program.account.brand.fetch(await createUniqueBrand())).name;
+
+    try {
+      await program.methods
+        .createBrand(uniqueBrandId(), takenName, "A brand reusing a taken name", null, null, null, [])
+        .accounts({
+          nameRegistry: findBrandNameRegistryPda(takenName),
+          tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        })
+        .signers([TRONIC_ADMIN_KEYPAIR])
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: BrandNameTaken");
+    }
+  });
+});
@@ -1,11 +1,13 @@
 // tests/common.ts
 import * as fs from 'fs';
 import * as path from 'path';
+import { createHash } from 'crypto';
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { CommunityEngagementProtocol } from "../target/types/community_engagement_protocol";
 import {
   TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
   getAssociatedTokenAddress,
 } from "@solana/spl-token";
@@ -28,6 +30,8 @@ export const TRONIC_ADMIN_KEYPAIR = anchor.web3.Keypair.fromSecretKey(keypairBuf
 export const TRONIC_ADMIN_PUBKEY = TRONIC_ADMIN_KEYPAIR.publicKey;
 
 export const TOKEN_METADATA_PROGRAM_ID = new anchor.web3.PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+export const BUBBLEGUM_PROGRAM_ID = new anchor.web3.PublicKey("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+export const ACCOUNT_COMPRESSION_PROGRAM_ID = new anchor.web3.PublicKey("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
 
 export const provider = anchor.AnchorProvider.env();
 anchor.setProvider(provider);
@@ -81,16 +85,44 @@ export async function initializeProgramState() {
   }
 }
 
-export async function createUniqueBrand() {
-  const name = `Test Brand ${Date.now()}`;
+let nextBrandId = Date.now();
+
+export function uniqueBrandId() {
+  return new anchor.BN(nextBrandId++);
+}
 
+export function findBrandPda(brandId: anchor.BN) {
   const [brandPda] = anchor.web3.PublicKey.findProgramAddressSync(
-    [Buffer.from("brand"), Buffer.from(name)],
+    [Buffer.from("brand"), brandId.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  );
+  return brandPda;
+}
+
+export function findBrandNameRegistryPda(name: string) {
+  const [registryPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("brand-name"), createHash("sha256").update(name).digest()],
     program.programId
   );
+  return registryPda;
+}
+
+export function findUserAchievementPda(achievement: PublicKey, user: PublicKey) {
+  const [userAchievementPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("user-achievement"), achievement.toBuffer(), user.toBuffer()],
+    program.programId
+  );
+  return userAchievementPda;
+}
+
+export async function createUniqueBrand() {
+  const brandId = uniqueBrandId();
+  const name = `Test Brand ${brandId.toString()}`;
+  const brandPda = findBrandPda(brandId);
 
   await program.methods
     .createBrand(
+      brandId,
       name,
       "A test brand for memberships",
       null,
@@ -99,6 +131,7 @@ export async function createUniqueBrand() {
       []
     )
     .accounts({
+      nameRegistry: findBrandNameRegistryPda(name),
       tronicAdmin: TRONIC_ADMIN_PUBKEY,
     })
     .signers([TRONIC_ADMIN_KEYPAIR])
@@ -109,4 +142,4 @@ export async function createUniqueBrand() {
 }
 
 // Export the token-related constants and functions
-export { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress };
\ No newline at end of file
+export { TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress };
\ No newline at end of file
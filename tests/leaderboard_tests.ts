@@ -0,0 +1,132 @@
+// tests/leaderboard_tests.ts
+import { expect } from 'chai';
+import * as anchor from "@coral-xyz/anchor";
+import { program, provider, createUniqueBrand, initializeProgramState, TOKEN_PROGRAM_ID, TRONIC_ADMIN_PUBKEY, TRONIC_ADMIN_KEYPAIR } from './common';
+
+describe("Leaderboard Tests", () => {
+  before(initializeProgramState);
+
+  it("Ranks users by the points of their awards", async () => {
+    const brandPda = await createUniqueBrand();
+
+    await program.methods
+      .createLeaderboard()
+      .accounts({
+        brand: brandPda,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+    const [leaderboard] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("leaderboard"), brandPda.toBuffer()],
+      program.programId
+    );
+
+    const createAchievement = async (name: string, points: number) => {
+      const achievement = anchor.web3.Keypair.generate();
+      const tokenMint = anchor.web3.Keypair.generate();
+      await program.methods
+        .createFungibleAchievement(name, `${name} Badge`, "Rank on the leaderboard", points, new anchor.BN(1000), false, null)
+        .accounts({
+          brand: brandPda,
+          achievement: achievement.publicKey,
+          tokenMint: tokenMint.publicKey,
+          tronicAdmin: TRONIC_ADMIN_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([achievement, tokenMint, TRONIC_ADMIN_KEYPAIR])
+        .rpc();
+      return { achievement: achievement.publicKey, tokenMint: tokenMint.publicKey };
+    };
+    const bronze = await createAchievement("Bronze", 10);
+    const gold = await createAchievement("Gold", 50);
+
+    const award = async (
+      user: anchor.web3.Keypair,
+      userAchievements: anchor.web3.PublicKey,
+      step: { achievement: anchor.web3.PublicKey, tokenMint: anchor.web3.PublicKey }
+    ) => {
+      await program.methods
+        .awardFungibleAchievement()
+        .accounts({
+          brand: brandPda,
+          achievement: step.achievement,
+          user: user.publicKey,
+          userAchievements,
+          authority: provider.wallet.publicKey,
+          tokenMint: step.tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+    };
+
+    const users = [anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate()];
+    const userAchievementsAccounts = [anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate()];
+    for (let i = 0; i < users.length; i++) {
+      await program.methods
+        .initializeUserAchievements()
+        .accounts({
+          userAchievements: userAchievementsAccounts[i].publicKey,
+          user: users[i].publicKey,
+          authority: provider.wallet.publicKey,
+        })
+        .signers([userAchievementsAccounts[i], users[i]])
+        .rpc();
+    }
+
+    await award(users[0], userAchievementsAccounts[0].publicKey, bronze);
+    await award(users[1], userAchievementsAccounts[1].publicKey, gold);
+    await award(users[0], userAchievementsAccounts[0].publicKey, gold);
+
+    const standings = await program.methods
+      .getLeaderboard(0, 10)
+      .accounts({
+        leaderboard,
+      })
+      .view();
+    expect(standings).to.have.lengthOf(2);
+    expect(standings[0].user.toBase58()).to.equal(users[0].publicKey.toBase58());
+    expect(standings[0].points.toNumber()).to.equal(60);
+    expect(standings[1].user.toBase58()).to.equal(users[1].publicKey.toBase58());
+    expect(standings[1].points.toNumber()).to.equal(50);
+
+    const secondPage = await program.methods
+      .getLeaderboard(1, 10)
+      .accounts({
+        leaderboard,
+      })
+      .view();
+    expect(secondPage).to.have.lengthOf(1);
+
+    // Awards always go through the brand's own leaderboard
+    const otherBrandPda = await createUniqueBrand();
+    await program.methods
+      .createLeaderboard()
+      .accounts({
+        brand: otherBrandPda,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+    const [otherLeaderboard] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("leaderboard"), otherBrandPda.toBuffer()],
+      program.programId
+    );
+    try {
+      await program.methods
+        .awardFungibleAchievement()
+        .accountsPartial({
+          brand: brandPda,
+          achievement: bronze.achievement,
+          user: users[1].publicKey,
+          userAchievements: userAchievementsAccounts[1].publicKey,
+          authority: provider.wallet.publicKey,
+          tokenMint: bronze.tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          leaderboard: otherLeaderboard,
+        })
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: ConstraintSeeds");
+    }
+  });
+});
@@ -1,7 +1,8 @@
 import * as anchor from "@coral-xyz/anchor";
 import { expect } from "chai";
-import { getAssociatedTokenAddress } from "@solana/spl-token";
-import { program, provider, brandList, initializeProgramState, createUniqueBrand, log, TOKEN_METADATA_PROGRAM_ID, TRONIC_ADMIN_PUBKEY, TRONIC_ADMIN_KEYPAIR, fundAccount } from './common';
+import { ExtensionType, getAccount, getAssociatedTokenAddress, getExtensionTypes, getMint, getTokenMetadata } from "@solana/spl-token";
+import { createHash } from "crypto";
+import { program, provider, brandList, initializeProgramState, createUniqueBrand, log, TOKEN_METADATA_PROGRAM_ID, TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TRONIC_ADMIN_PUBKEY, TRONIC_ADMIN_KEYPAIR, fundAccount } from './common';
 
 describe("Membership Tests", () => {
   before(initializeProgramState);
@@ -72,7 +73,7 @@ describe("Membership Tests", () => {
 
     try {
       await program.methods
-        .createMembershipTier(tierId, duration, isOpen, tierUri)
+        .createMembershipTier(tierId, duration, isOpen, tierUri, null, false)
         .accounts({
           membershipData: membershipData.publicKey,
           tronicAdmin: TRONIC_ADMIN_PUBKEY,
@@ -148,6 +149,7 @@ describe("Membership Tests", () => {
           recipient: recipient.publicKey,
           metadata: metadataAddress,
           masterEdition: masterEditionAddress,
+          tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([mint])
         .rpc();
@@ -216,7 +218,9 @@ describe("Membership Tests", () => {
               tier.id,
               new anchor.BN(tier.duration),
               tier.isOpen,
-              tier.uri
+              tier.uri,
+              null,
+              false
             )
             .accounts({
               membershipData: membershipData.publicKey,
@@ -313,6 +317,7 @@ describe("Membership Tests", () => {
                     recipient: recipient.publicKey,
                     metadata: metadataAddress,
                     masterEdition: masterEditionAddress,
+                    tokenProgram: TOKEN_PROGRAM_ID,
                 })
                 .signers([mint])
                 .rpc();
@@ -457,7 +462,9 @@ describe("Membership Tests", () => {
         uniqueTierId,
         new anchor.BN(30 * 24 * 60 * 60),
         true,
-        "basic.json"
+        "basic.json",
+        null,
+        false
       )
       .accounts({
         membershipData: membershipData.publicKey,
@@ -579,6 +586,7 @@ describe("Membership Tests", () => {
           tronicAdmin: nonAdminKeypair.publicKey,
           metadata: metadataAddress,
           masterEdition: masterEditionAddress,
+          tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([mint, nonAdminKeypair])
         .rpc();
@@ -589,4 +597,658 @@ describe("Membership Tests", () => {
       log("Correctly failed to mint membership with non-admin signer");
     }
   });
-});
\ No newline at end of file
+
+  it("Updates, closes and retires a membership tier", async () => {
+    const brandPda = await createUniqueBrand();
+    const tierMembershipData = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .initializeMembership(
+        new anchor.BN(3),
+        "Tier Management Membership",
+        "TIER",
+        "https://example.com/",
+        new anchor.BN(1000),
+        true,
+        5
+      )
+      .accounts({
+        brand: brandPda,
+        membershipData: tierMembershipData.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([tierMembershipData, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .createMembershipTier("GOLD", new anchor.BN(30 * 24 * 60 * 60), true, "gold.json", null, false)
+      .accounts({
+        membershipData: tierMembershipData.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+      })
+      .rpc();
+
+    try {
+      await program.methods
+        .createMembershipTier("PLATINUM-PLUS", new anchor.BN(30 * 24 * 60 * 60), true, "platinum.json", null, false)
+        .accounts({
+          membershipData: tierMembershipData.publicKey,
+          tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        })
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: TierIdTooLong");
+    }
+
+    try {
+      await program.methods
+        .createMembershipTier("SILVER", new anchor.BN(30 * 24 * 60 * 60), true, "x".repeat(201), null, false)
+        .accounts({
+          membershipData: tierMembershipData.publicKey,
+          tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        })
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: UriTooLong");
+    }
+
+    // The account is sized for ten tiers
+    const oversizedMembershipData = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .initializeMembership(new anchor.BN(8), "Oversized", "BIG", "https://example.com/", new anchor.BN(1000), true, 11)
+        .accounts({
+          brand: brandPda,
+          membershipData: oversizedMembershipData.publicKey,
+          tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        })
+        .signers([oversizedMembershipData, TRONIC_ADMIN_KEYPAIR])
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: TooManyTiers");
+    }
+
+    const tierAccounts = {
+      membershipData: tierMembershipData.publicKey,
+      authority: TRONIC_ADMIN_PUBKEY,
+    };
+
+    await program.methods
+      .updateMembershipTier("GOLD", new anchor.BN(365 * 24 * 60 * 60), "gold-v2.json", null)
+      .accounts(tierAccounts)
+      .signers([TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+    await program.methods
+      .setTierOpen("GOLD", false)
+      .accounts(tierAccounts)
+      .signers([TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+    await program.methods
+      .retireMembershipTier("GOLD")
+      .accounts(tierAccounts)
+      .signers([TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    const account = await program.account.membershipData.fetch(tierMembershipData.publicKey);
+    expect(account.tiers.length).to.equal(1);
+    expect(account.tiers[0].duration.toNumber()).to.equal(365 * 24 * 60 * 60);
+    expect(account.tiers[0].tierUri).to.equal("gold-v2.json");
+    expect(account.tiers[0].isOpen).to.be.false;
+    expect(account.tiers[0].retired).to.be.true;
+
+    const mint = anchor.web3.Keypair.generate();
+    const recipient = anchor.web3.Keypair.generate();
+    const metadataAddress = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("metadata"),
+        TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+        mint.publicKey.toBuffer(),
+      ],
+      TOKEN_METADATA_PROGRAM_ID
+    )[0];
+    const masterEditionAddress = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("metadata"),
+        TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+        mint.publicKey.toBuffer(),
+        Buffer.from("edition"),
+      ],
+      TOKEN_METADATA_PROGRAM_ID
+    )[0];
+
+    try {
+      await program.methods
+        .mintMembership(0)
+        .accounts({
+          membershipData: tierMembershipData.publicKey,
+          mint: mint.publicKey,
+          recipient: recipient.publicKey,
+          metadata: metadataAddress,
+          masterEdition: masterEditionAddress,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([mint])
+        .rpc();
+
+      expect.fail("Should not be able to mint a retired tier");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: TierRetired");
+    }
+  });
+
+  it("Restricts closed tiers to allowlisted recipients and enforces tier supply", async () => {
+    const brandPda = await createUniqueBrand();
+    const vipMembershipData = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .initializeMembership(
+        new anchor.BN(4),
+        "VIP Membership",
+        "VIP",
+        "https://example.com/",
+        new anchor.BN(1000),
+        true,
+        5
+      )
+      .accounts({
+        brand: brandPda,
+        membershipData: vipMembershipData.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([vipMembershipData, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .createMembershipTier("VIP", new anchor.BN(30 * 24 * 60 * 60), false, "vip.json", new anchor.BN(1), false)
+      .accounts({
+        membershipData: vipMembershipData.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+      })
+      .rpc();
+
+    const mintTier = async (tierIndex: number, recipient: anchor.web3.PublicKey, allowlistEntry: anchor.web3.PublicKey | null) => {
+      const mint = anchor.web3.Keypair.generate();
+      const [metadataAddress] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.publicKey.toBuffer()],
+        TOKEN_METADATA_PROGRAM_ID
+      );
+      const [masterEditionAddress] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.publicKey.toBuffer(), Buffer.from("edition")],
+        TOKEN_METADATA_PROGRAM_ID
+      );
+      await program.methods
+        .mintMembership(tierIndex)
+        .accounts({
+          membershipData: vipMembershipData.publicKey,
+          mint: mint.publicKey,
+          recipient,
+          metadata: metadataAddress,
+          masterEdition: masterEditionAddress,
+          allowlistEntry,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([mint])
+        .rpc();
+    };
+
+    const addToAllowlist = async (recipient: anchor.web3.PublicKey) => {
+      const [entry] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tier-allowlist"), vipMembershipData.publicKey.toBuffer(), Buffer.from("VIP"), recipient.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .addToTierAllowlist("VIP", recipient)
+        .accounts({
+          membershipData: vipMembershipData.publicKey,
+          authority: TRONIC_ADMIN_PUBKEY,
+        })
+        .signers([TRONIC_ADMIN_KEYPAIR])
+        .rpc();
+      return entry;
+    };
+
+    const firstRecipient = anchor.web3.Keypair.generate().publicKey;
+    const secondRecipient = anchor.web3.Keypair.generate().publicKey;
+
+    try {
+      await mintTier(1, firstRecipient, null);
+      expect.fail("Should not be able to mint an out-of-range tier index");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: InvalidTierIndex");
+    }
+
+    try {
+      await mintTier(0, firstRecipient, null);
+      expect.fail("Should not be able to mint a closed tier without an allowlist entry");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: NotOnAllowlist");
+    }
+
+    await mintTier(0, firstRecipient, await addToAllowlist(firstRecipient));
+
+    try {
+      await mintTier(0, secondRecipient, await addToAllowlist(secondRecipient));
+      expect.fail("Should not be able to mint past the tier supply");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: TierSupplyReached");
+    }
+
+    const account = await program.account.membershipData.fetch(vipMembershipData.publicKey);
+    expect(account.tiers[0].minted.toNumber()).to.equal(1);
+  });
+
+  it("Joins a closed tier with an invite code or a Merkle proof", async () => {
+    const brandPda = await createUniqueBrand();
+    const inviteMembershipData = anchor.web3.Keypair.generate();
+    const sha256 = (data: Buffer) => createHash("sha256").update(data).digest();
+
+    await program.methods
+      .initializeMembership(
+        new anchor.BN(5),
+        "Invite Membership",
+        "INV",
+        "https://example.com/",
+        new anchor.BN(1000),
+        true,
+        5
+      )
+      .accounts({
+        brand: brandPda,
+        membershipData: inviteMembershipData.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([inviteMembershipData, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .createMembershipTier("INNER", new anchor.BN(30 * 24 * 60 * 60), false, "inner.json", null, false)
+      .accounts({
+        membershipData: inviteMembershipData.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+      })
+      .rpc();
+
+    const findAllowlistEntry = (recipient: anchor.web3.PublicKey) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("tier-allowlist"), inviteMembershipData.publicKey.toBuffer(), Buffer.from("INNER"), recipient.toBuffer()],
+        program.programId
+      )[0];
+
+    // Invite code path
+    const code = "WELCOME-2024";
+    const codeHash = sha256(Buffer.from(code));
+    const [inviteCodePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("invite-code"), inviteMembershipData.publicKey.toBuffer(), codeHash],
+      program.programId
+    );
+
+    await program.methods
+      .addInviteCode("INNER", Array.from(codeHash))
+      .accounts({
+        membershipData: inviteMembershipData.publicKey,
+        authority: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    const invitee = anchor.web3.Keypair.generate();
+    await fundAccount(program.provider.connection, invitee.publicKey);
+    await program.methods
+      .joinWithInvite("INNER", [], code)
+      .accounts({
+        membershipData: inviteMembershipData.publicKey,
+        inviteCode: inviteCodePda,
+        recipient: invitee.publicKey,
+      })
+      .signers([invitee])
+      .rpc();
+
+    const inviteCodeAccount = await program.account.inviteCode.fetch(inviteCodePda);
+    expect(inviteCodeAccount.consumed).to.be.true;
+    expect(inviteCodeAccount.consumedBy.toString()).to.equal(invitee.publicKey.toString());
+    const entry = await program.account.tierAllowlistEntry.fetch(findAllowlistEntry(invitee.publicKey));
+    expect(entry.recipient.toString()).to.equal(invitee.publicKey.toString());
+
+    // A consumed code can't be reused
+    const latecomer = anchor.web3.Keypair.generate();
+    await fundAccount(program.provider.connection, latecomer.publicKey);
+    try {
+      await program.methods
+        .joinWithInvite("INNER", [], code)
+        .accounts({
+          membershipData: inviteMembershipData.publicKey,
+          inviteCode: inviteCodePda,
+          recipient: latecomer.publicKey,
+        })
+        .signers([latecomer])
+        .rpc();
+      expect.fail("Should not be able to reuse an invite code");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: InviteCodeConsumed");
+    }
+
+    // Merkle proof path: a two-leaf tree of sha256(pubkey) leaves with sorted-pair hashing
+    const member = anchor.web3.Keypair.generate();
+    const other = anchor.web3.Keypair.generate();
+    await fundAccount(program.provider.connection, member.publicKey);
+    const leaf = sha256(member.publicKey.toBuffer());
+    const sibling = sha256(other.publicKey.toBuffer());
+    const root = sha256(Buffer.compare(leaf, sibling) <= 0 ? Buffer.concat([leaf, sibling]) : Buffer.concat([sibling, leaf]));
+
+    await program.methods
+      .setTierAllowlistRoot("INNER", Array.from(root))
+      .accounts({
+        membershipData: inviteMembershipData.publicKey,
+        authority: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .joinWithInvite("INNER", [Array.from(sibling)], null)
+      .accounts({
+        membershipData: inviteMembershipData.publicKey,
+        inviteCode: null,
+        recipient: member.publicKey,
+      })
+      .signers([member])
+      .rpc();
+
+    const merkleEntry = await program.account.tierAllowlistEntry.fetch(findAllowlistEntry(member.publicKey));
+    expect(merkleEntry.tierId).to.equal("INNER");
+  });
+
+  it("Mints a soulbound Token-2022 membership without Metaplex accounts", async () => {
+    const brandPda = await createUniqueBrand();
+    const membershipData2022 = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .initializeMembership(
+        new anchor.BN(6),
+        "Token-2022 Membership",
+        "T22",
+        "https://example.com/",
+        new anchor.BN(1000),
+        true,
+        5
+      )
+      .accounts({
+        brand: brandPda,
+        membershipData: membershipData2022.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([membershipData2022, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .createMembershipTier("SOUL", new anchor.BN(30 * 24 * 60 * 60), true, "soul.json", null, true)
+      .accounts({
+        membershipData: membershipData2022.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+      })
+      .rpc();
+
+    const mint = anchor.web3.Keypair.generate();
+    const recipient = anchor.web3.Keypair.generate();
+    await program.methods
+      .mintMembership(0)
+      .accountsPartial({
+        membershipData: membershipData2022.publicKey,
+        mint: mint.publicKey,
+        recipient: recipient.publicKey,
+        metadata: null,
+        masterEdition: null,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        tokenMetadataProgram: null,
+      })
+      .signers([mint])
+      .rpc();
+
+    const mintAccount = await getMint(provider.connection, mint.publicKey, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(mintAccount.mintAuthority).to.be.null;
+    expect(getExtensionTypes(mintAccount.tlvData)).to.include(ExtensionType.NonTransferable);
+    const metadata = await getTokenMetadata(provider.connection, mint.publicKey);
+    expect(metadata.name).to.equal("Token-2022 Membership");
+    expect(metadata.symbol).to.equal("T22");
+    expect(metadata.uri).to.equal("https://example.com/soul.json");
+
+    const tokenAccountAddress = await getAssociatedTokenAddress(
+      mint.publicKey,
+      recipient.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const tokenAccount = await getAccount(provider.connection, tokenAccountAddress, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(Number(tokenAccount.amount)).to.equal(1);
+    expect(getExtensionTypes(mintAccount.tlvData)).to.include(ExtensionType.PermanentDelegate);
+
+    // The brand's mint authority is the permanent delegate, so the brand can revoke it
+    await program.methods
+      .revokeSoulbound(new anchor.BN(1))
+      .accounts({
+        brand: brandPda,
+        mint: mint.publicKey,
+        tokenAccount: tokenAccountAddress,
+        authority: TRONIC_ADMIN_PUBKEY,
+        edition: null,
+        tokenMetadataProgram: null,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    const revokedAccount = await getAccount(provider.connection, tokenAccountAddress, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(Number(revokedAccount.amount)).to.equal(0);
+  });
+
+  it("Only removes membership tiers that have never been minted", async () => {
+    const brandPda = await createUniqueBrand();
+    const removalMembershipData = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .initializeMembership(
+        new anchor.BN(7),
+        "Tier Removal Membership",
+        "RMV",
+        "https://example.com/",
+        new anchor.BN(1000),
+        true,
+        5
+      )
+      .accounts({
+        brand: brandPda,
+        membershipData: removalMembershipData.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([removalMembershipData, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    for (const tierId of ["MINTED", "UNUSED"]) {
+      await program.methods
+        .createMembershipTier(tierId, new anchor.BN(30 * 24 * 60 * 60), true, `${tierId}.json`, null, false)
+        .accounts({
+          membershipData: removalMembershipData.publicKey,
+          tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        })
+        .rpc();
+    }
+
+    const mint = anchor.web3.Keypair.generate();
+    await program.methods
+      .mintMembership(0)
+      .accountsPartial({
+        membershipData: removalMembershipData.publicKey,
+        mint: mint.publicKey,
+        recipient: anchor.web3.Keypair.generate().publicKey,
+        metadata: null,
+        masterEdition: null,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        tokenMetadataProgram: null,
+      })
+      .signers([mint])
+      .rpc();
+
+    const tierAccounts = {
+      membershipData: removalMembershipData.publicKey,
+      authority: TRONIC_ADMIN_PUBKEY,
+    };
+
+    try {
+      await program.methods
+        .removeMembershipTier("MINTED")
+        .accounts(tierAccounts)
+        .signers([TRONIC_ADMIN_KEYPAIR])
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: TierHasMemberships");
+    }
+
+    await program.methods
+      .removeMembershipTier("UNUSED")
+      .accounts(tierAccounts)
+      .signers([TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    const account = await program.account.membershipData.fetch(removalMembershipData.publicKey);
+    expect(account.tiers.map((tier) => tier.tierId)).to.deep.equal(["MINTED"]);
+    expect(account.tiers[0].minted.toNumber()).to.equal(1);
+
+    // A tier's cap can't drop below what it has already minted
+    try {
+      await program.methods
+        .updateMembershipTier("MINTED", new anchor.BN(30 * 24 * 60 * 60), "MINTED.json", new anchor.BN(0))
+        .accounts(tierAccounts)
+        .signers([TRONIC_ADMIN_KEYPAIR])
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: TierSupplyBelowMinted");
+    }
+    await program.methods
+      .updateMembershipTier("MINTED", new anchor.BN(30 * 24 * 60 * 60), "MINTED.json", new anchor.BN(1))
+      .accounts(tierAccounts)
+      .signers([TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+    const capped = await program.account.membershipData.fetch(removalMembershipData.publicKey);
+    expect(capped.tiers[0].maxSupply.toNumber()).to.equal(1);
+  });
+
+  it("Freezes soulbound SPL Token memberships through the master edition", async () => {
+    const brandPda = await createUniqueBrand();
+    const soulMembershipData = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .initializeMembership(
+        new anchor.BN(8),
+        "Soulbound Membership",
+        "SOUL",
+        "https://example.com/",
+        new anchor.BN(1000),
+        true,
+        5
+      )
+      .accounts({
+        brand: brandPda,
+        membershipData: soulMembershipData.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([soulMembershipData, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .createMembershipTier("SOUL", new anchor.BN(30 * 24 * 60 * 60), true, "soul.json", null, true)
+      .accounts({
+        membershipData: soulMembershipData.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+      })
+      .rpc();
+
+    const editionOf = (mint: anchor.web3.PublicKey) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer(), Buffer.from("edition")],
+        TOKEN_METADATA_PROGRAM_ID
+      )[0];
+    const mintSoulbound = (mint: anchor.web3.Keypair, recipient: anchor.web3.Keypair, recipientSigns: boolean) =>
+      program.methods
+        .mintMembership(0)
+        .accounts({
+          membershipData: soulMembershipData.publicKey,
+          mint: mint.publicKey,
+          recipient: recipient.publicKey,
+          metadata: anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.publicKey.toBuffer()],
+            TOKEN_METADATA_PROGRAM_ID
+          )[0],
+          masterEdition: editionOf(mint.publicKey),
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers(recipientSigns ? [mint, recipient] : [mint])
+        .rpc();
+
+    const [mintAuthority] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("mint-authority"), brandPda.toBuffer()],
+      program.programId
+    );
+
+    try {
+      await mintSoulbound(anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(), false);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: SoulboundHolderSignatureRequired");
+    }
+
+    // The brand revokes one membership without its holder
+    const revokedMint = anchor.web3.Keypair.generate();
+    const revokedHolder = anchor.web3.Keypair.generate();
+    await mintSoulbound(revokedMint, revokedHolder, true);
+
+    const revokedAccountAddress = await getAssociatedTokenAddress(revokedMint.publicKey, revokedHolder.publicKey);
+    const frozenAccount = await getAccount(provider.connection, revokedAccountAddress);
+    expect(frozenAccount.isFrozen).to.be.true;
+    expect(frozenAccount.delegate.toString()).to.equal(mintAuthority.toString());
+
+    await program.methods
+      .revokeSoulbound(new anchor.BN(1))
+      .accounts({
+        brand: brandPda,
+        mint: revokedMint.publicKey,
+        tokenAccount: revokedAccountAddress,
+        authority: TRONIC_ADMIN_PUBKEY,
+        edition: editionOf(revokedMint.publicKey),
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+    expect(Number((await getAccount(provider.connection, revokedAccountAddress)).amount)).to.equal(0);
+
+    // And a holder burns another on their own
+    const burnedMint = anchor.web3.Keypair.generate();
+    const burnedHolder = anchor.web3.Keypair.generate();
+    await mintSoulbound(burnedMint, burnedHolder, true);
+
+    const burnedAccountAddress = await getAssociatedTokenAddress(burnedMint.publicKey, burnedHolder.publicKey);
+    await program.methods
+      .thawAndBurn(new anchor.BN(1))
+      .accounts({
+        brand: brandPda,
+        mint: burnedMint.publicKey,
+        tokenAccount: burnedAccountAddress,
+        holder: burnedHolder.publicKey,
+        freezeAuthority: null,
+        edition: editionOf(burnedMint.publicKey),
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([burnedHolder])
+      .rpc();
+
+    const burnedAccount = await getAccount(provider.connection, burnedAccountAddress);
+    expect(burnedAccount.isFrozen).to.be.false;
+    expect(Number(burnedAccount.amount)).to.equal(0);
+  });
+});
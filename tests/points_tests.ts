@@ -0,0 +1,384 @@
+// tests/points_tests.ts
+import { expect } from 'chai';
+import * as anchor from "@coral-xyz/anchor";
+import { program, provider, createUniqueBrand, initializeProgramState, fundAccount, TOKEN_PROGRAM_ID, TRONIC_ADMIN_PUBKEY, TRONIC_ADMIN_KEYPAIR } from './common';
+
+describe("Points Tests", () => {
+  before(initializeProgramState);
+
+  it("Redeems points within the brand's expiry policy", async () => {
+    const brandPda = await createUniqueBrand();
+    const user = anchor.web3.Keypair.generate();
+    const userAchievements = anchor.web3.Keypair.generate();
+
+    try {
+      await program.methods
+        .setPointsPolicy({ fixed: { days: 0 } })
+        .accounts({
+          brand: brandPda,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: InvalidPointsPolicy");
+    }
+
+    await program.methods
+      .setPointsPolicy({ fixed: { days: 30 } })
+      .accounts({
+        brand: brandPda,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    await program.methods
+      .initializeUserAchievements()
+      .accounts({
+        userAchievements: userAchievements.publicKey,
+        user: user.publicKey,
+        authority: provider.wallet.publicKey,
+      })
+      .signers([userAchievements, user])
+      .rpc();
+
+    const achievement = anchor.web3.Keypair.generate();
+    const tokenMint = anchor.web3.Keypair.generate();
+    await program.methods
+      .createFungibleAchievement("Visit", "Store Visit", "Visit a store", 40, new anchor.BN(1000), false, null)
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([achievement, tokenMint, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .awardFungibleAchievement()
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        user: user.publicKey,
+        userAchievements: userAchievements.publicKey,
+        authority: provider.wallet.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const [userPoints] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user-points"), brandPda.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    const balance = () =>
+      program.methods
+        .getPointsBalance()
+        .accounts({
+          userPoints,
+        })
+        .view();
+    expect((await balance()).toNumber()).to.equal(40);
+
+    const redeem = (amount: number) =>
+      program.methods
+        .redeemPoints(new anchor.BN(amount))
+        .accounts({
+          brand: brandPda,
+          user: user.publicKey,
+          authority: provider.wallet.publicKey,
+        })
+        .signers([user])
+        .rpc();
+
+    await redeem(15);
+    expect((await balance()).toNumber()).to.equal(25);
+
+    try {
+      await redeem(100);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: InsufficientPoints");
+    }
+
+    // Nothing has expired yet, so the crank leaves the points alone
+    await program.methods
+      .expirePoints()
+      .accounts({
+        userPoints,
+      })
+      .rpc();
+
+    const points = await program.account.userPoints.fetch(userPoints);
+    expect(points.balance.toNumber()).to.equal(25);
+    expect(points.expired.toNumber()).to.equal(0);
+    expect(points.buckets).to.have.lengthOf(1);
+  });
+
+  it("Exchanges points between partnered brands within the daily cap", async () => {
+    const fromBrand = await createUniqueBrand();
+    const toBrand = await createUniqueBrand();
+    const user = anchor.web3.Keypair.generate();
+    const userAchievements = anchor.web3.Keypair.generate();
+    await fundAccount(provider.connection, user.publicKey);
+
+    await program.methods
+      .setPointsPolicy({ none: {} })
+      .accounts({
+        brand: fromBrand,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    await program.methods
+      .initializeUserAchievements()
+      .accounts({
+        userAchievements: userAchievements.publicKey,
+        user: user.publicKey,
+        authority: provider.wallet.publicKey,
+      })
+      .signers([userAchievements, user])
+      .rpc();
+
+    const achievement = anchor.web3.Keypair.generate();
+    const tokenMint = anchor.web3.Keypair.generate();
+    await program.methods
+      .createFungibleAchievement("Visit", "Store Visit", "Visit a store", 40, new anchor.BN(1000), false, null)
+      .accounts({
+        brand: fromBrand,
+        achievement: achievement.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([achievement, tokenMint, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .awardFungibleAchievement()
+      .accounts({
+        brand: fromBrand,
+        achievement: achievement.publicKey,
+        user: user.publicKey,
+        userAchievements: userAchievements.publicKey,
+        authority: provider.wallet.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    // Two points of the first brand buy one of the second, up to 30 a day
+    await program.methods
+      .createPointsExchange(5000, new anchor.BN(30))
+      .accounts({
+        fromBrand,
+        toBrand,
+        fromAuthority: provider.wallet.publicKey,
+        toAuthority: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    await program.methods
+      .createLeaderboard()
+      .accounts({
+        brand: toBrand,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    const exchange = (amount: number) =>
+      program.methods
+        .exchangePoints(new anchor.BN(amount))
+        .accounts({
+          fromBrand,
+          toBrand,
+          user: user.publicKey,
+        })
+        .signers([user])
+        .rpc();
+
+    await exchange(20);
+
+    const pointsOf = (brand: anchor.web3.PublicKey) =>
+      program.account.userPoints.fetch(
+        anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("user-points"), brand.toBuffer(), user.publicKey.toBuffer()],
+          program.programId
+        )[0]
+      );
+    expect((await pointsOf(fromBrand)).balance.toNumber()).to.equal(20);
+    expect((await pointsOf(toBrand)).balance.toNumber()).to.equal(10);
+
+    // Exchanged points rank the user on the second brand's leaderboard
+    const [leaderboard] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("leaderboard"), toBrand.toBuffer()],
+      program.programId
+    );
+    const standings = (await program.account.leaderboard.fetch(leaderboard)).standings;
+    expect(standings).to.have.lengthOf(1);
+    expect(standings[0].user.toBase58()).to.equal(user.publicKey.toBase58());
+    expect(standings[0].points.toNumber()).to.equal(10);
+
+    try {
+      await exchange(20);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: ExchangeCapExceeded");
+    }
+    expect((await pointsOf(fromBrand)).balance.toNumber()).to.equal(20);
+  });
+
+  it("Keeps points forever for brands without an expiry policy", async () => {
+    const brandPda = await createUniqueBrand();
+    const user = anchor.web3.Keypair.generate();
+    const userAchievements = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .initializeUserAchievements()
+      .accounts({
+        userAchievements: userAchievements.publicKey,
+        user: user.publicKey,
+        authority: provider.wallet.publicKey,
+      })
+      .signers([userAchievements, user])
+      .rpc();
+
+    const achievement = anchor.web3.Keypair.generate();
+    const tokenMint = anchor.web3.Keypair.generate();
+    await program.methods
+      .createFungibleAchievement("Visit", "Store Visit", "Visit a store", 40, new anchor.BN(1000), false, null)
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([achievement, tokenMint, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .awardFungibleAchievement()
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        user: user.publicKey,
+        userAchievements: userAchievements.publicKey,
+        authority: provider.wallet.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const [userPoints] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user-points"), brandPda.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    const balance = await program.methods
+      .getPointsBalance()
+      .accountsPartial({
+        userPoints,
+        pointsPolicy: null,
+      })
+      .view();
+    expect(balance.toNumber()).to.equal(40);
+
+    await program.methods
+      .redeemPoints(new anchor.BN(15))
+      .accountsPartial({
+        brand: brandPda,
+        user: user.publicKey,
+        authority: provider.wallet.publicKey,
+        pointsPolicy: null,
+        attester: null,
+      })
+      .signers([user])
+      .rpc();
+
+    await program.methods
+      .expirePoints()
+      .accountsPartial({
+        userPoints,
+        pointsPolicy: null,
+      })
+      .rpc();
+
+    const points = await program.account.userPoints.fetch(userPoints);
+    expect(points.balance.toNumber()).to.equal(25);
+    expect(points.expired.toNumber()).to.equal(0);
+  });
+
+  it("Exchanges points out of brands without an expiry policy", async () => {
+    const fromBrand = await createUniqueBrand();
+    const toBrand = await createUniqueBrand();
+    const user = anchor.web3.Keypair.generate();
+    const userAchievements = anchor.web3.Keypair.generate();
+    await fundAccount(provider.connection, user.publicKey);
+
+    await program.methods
+      .initializeUserAchievements()
+      .accounts({
+        userAchievements: userAchievements.publicKey,
+        user: user.publicKey,
+        authority: provider.wallet.publicKey,
+      })
+      .signers([userAchievements, user])
+      .rpc();
+
+    const achievement = anchor.web3.Keypair.generate();
+    const tokenMint = anchor.web3.Keypair.generate();
+    await program.methods
+      .createFungibleAchievement("Visit", "Store Visit", "Visit a store", 40, new anchor.BN(1000), false, null)
+      .accounts({
+        brand: fromBrand,
+        achievement: achievement.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([achievement, tokenMint, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .awardFungibleAchievement()
+      .accounts({
+        brand: fromBrand,
+        achievement: achievement.publicKey,
+        user: user.publicKey,
+        userAchievements: userAchievements.publicKey,
+        authority: provider.wallet.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    await program.methods
+      .createPointsExchange(10000, new anchor.BN(100))
+      .accounts({
+        fromBrand,
+        toBrand,
+        fromAuthority: provider.wallet.publicKey,
+        toAuthority: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    await program.methods
+      .exchangePoints(new anchor.BN(40))
+      .accountsPartial({
+        fromBrand,
+        toBrand,
+        user: user.publicKey,
+        fromPointsPolicy: null,
+      })
+      .signers([user])
+      .rpc();
+
+    const [toUserPoints] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user-points"), toBrand.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    expect((await program.account.userPoints.fetch(toUserPoints)).balance.toNumber()).to.equal(40);
+  });
+});
@@ -0,0 +1,217 @@
+// tests/quest_tests.ts
+import { expect } from 'chai';
+import * as anchor from "@coral-xyz/anchor";
+import { program, provider, createUniqueBrand, initializeProgramState, TOKEN_PROGRAM_ID, TRONIC_ADMIN_PUBKEY, TRONIC_ADMIN_KEYPAIR, findUserAchievementPda } from './common';
+
+describe("Quest Tests", () => {
+  before(initializeProgramState);
+
+  it("Completes a quest and issues its reward and bonus points", async () => {
+    const brandPda = await createUniqueBrand();
+    const user = anchor.web3.Keypair.generate();
+    const userAchievements = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .initializeUserAchievements()
+      .accounts({
+        userAchievements: userAchievements.publicKey,
+        user: user.publicKey,
+        authority: provider.wallet.publicKey,
+      })
+      .signers([userAchievements, user])
+      .rpc();
+
+    const createStep = async (name: string) => {
+      const achievement = anchor.web3.Keypair.generate();
+      const tokenMint = anchor.web3.Keypair.generate();
+      await program.methods
+        .createFungibleAchievement(name, `${name} step`, "Finish the step", 10, new anchor.BN(1000), false, null)
+        .accounts({
+          brand: brandPda,
+          achievement: achievement.publicKey,
+          tokenMint: tokenMint.publicKey,
+          tronicAdmin: TRONIC_ADMIN_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([achievement, tokenMint, TRONIC_ADMIN_KEYPAIR])
+        .rpc();
+      return { achievement: achievement.publicKey, tokenMint: tokenMint.publicKey };
+    };
+    const awardStep = async (step: { achievement: anchor.web3.PublicKey, tokenMint: anchor.web3.PublicKey }) => {
+      await program.methods
+        .awardFungibleAchievement()
+        .accounts({
+          brand: brandPda,
+          achievement: step.achievement,
+          user: user.publicKey,
+          userAchievements: userAchievements.publicKey,
+          authority: provider.wallet.publicKey,
+          tokenMint: step.tokenMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      return findUserAchievementPda(step.achievement, user.publicKey);
+    };
+
+    const visit = await createStep("Visit");
+    const share = await createStep("Share");
+
+    const reward = anchor.web3.Keypair.generate();
+    const rewardMint = anchor.web3.Keypair.generate();
+    await program.methods
+      .createFungibleReward("Quest Tokens", "Tokens for finishing the quest", new anchor.BN(1000))
+      .accounts({
+        brand: brandPda,
+        reward: reward.publicKey,
+        tokenMint: rewardMint.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([reward, rewardMint, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    const quest = anchor.web3.Keypair.generate();
+    await program.methods
+      .createQuest("Launch Week", [visit.achievement, share.achievement], true, null, new anchor.BN(50), new anchor.BN(100))
+      .accounts({
+        brand: brandPda,
+        quest: quest.publicKey,
+        reward: reward.publicKey,
+        authority: provider.wallet.publicKey,
+      })
+      .remainingAccounts([visit, share].map(step => ({ pubkey: step.achievement, isSigner: false, isWritable: false })))
+      .signers([quest])
+      .rpc();
+
+    const seasonId = 1;
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods
+      .createSeason(seasonId, new anchor.BN(now - 60), new anchor.BN(now + 3600))
+      .accounts({
+        brand: brandPda,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+    const seasonIdBytes = Buffer.alloc(4);
+    seasonIdBytes.writeUInt32LE(seasonId);
+    const [season] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("season"), brandPda.toBuffer(), seasonIdBytes],
+      program.programId
+    );
+    const [userSeasonPoints] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user-season-points"), season.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const userTokenAccount = anchor.utils.token.associatedAddress({
+      mint: rewardMint.publicKey,
+      owner: user.publicKey,
+    });
+    const completeQuest = (records: anchor.web3.PublicKey[]) =>
+      program.methods
+        .completeQuest()
+        .accounts({
+          brand: brandPda,
+          quest: quest.publicKey,
+          user: user.publicKey,
+          authority: provider.wallet.publicKey,
+          reward: reward.publicKey,
+          tokenMint: rewardMint.publicKey,
+          userTokenAccount,
+          season,
+          userSeasonPoints,
+        })
+        .remainingAccounts(records.map(pubkey => ({ pubkey, isSigner: false, isWritable: false })))
+        .rpc();
+
+    const visitRecord = await awardStep(visit);
+    try {
+      await completeQuest([visitRecord]);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: QuestIncomplete");
+    }
+
+    const shareRecord = await awardStep(share);
+    await completeQuest([visitRecord, shareRecord]);
+
+    const balance = await provider.connection.getTokenAccountBalance(userTokenAccount);
+    expect(balance.value.uiAmount).to.equal(50);
+
+    const [userPoints] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user-points"), brandPda.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    const points = await program.account.userPoints.fetch(userPoints);
+    // Each step credits its 10 points on award, plus the quest's bonus
+    expect(points.balance.toNumber()).to.equal(120);
+
+    // The bonus also counts towards the season passed with the completion
+    const seasonPoints = await program.account.userSeasonPoints.fetch(userSeasonPoints);
+    expect(seasonPoints.points.toNumber()).to.equal(100);
+
+    const questAccount = await program.account.quest.fetch(quest.publicKey);
+    expect(questAccount.completedCount.toNumber()).to.equal(1);
+
+    // The UserQuest PDA already exists, so a quest can't be completed twice
+    try {
+      await completeQuest([visitRecord, shareRecord]);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("already in use");
+    }
+  });
+
+  it("Only creates quests over the brand's own achievements", async () => {
+    const brandPda = await createUniqueBrand();
+    const otherBrandPda = await createUniqueBrand();
+
+    const createStep = async (brand: anchor.web3.PublicKey, name: string) => {
+      const achievement = anchor.web3.Keypair.generate();
+      const tokenMint = anchor.web3.Keypair.generate();
+      await program.methods
+        .createFungibleAchievement(name, `${name} step`, "Finish the step", 10, new anchor.BN(1000), false, null)
+        .accounts({
+          brand,
+          achievement: achievement.publicKey,
+          tokenMint: tokenMint.publicKey,
+          tronicAdmin: TRONIC_ADMIN_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([achievement, tokenMint, TRONIC_ADMIN_KEYPAIR])
+        .rpc();
+      return achievement.publicKey;
+    };
+    const ownStep = await createStep(brandPda, "Visit");
+    const foreignStep = await createStep(otherBrandPda, "Share");
+
+    const createQuest = (steps: anchor.web3.PublicKey[], stepAccounts: anchor.web3.PublicKey[]) => {
+      const quest = anchor.web3.Keypair.generate();
+      return program.methods
+        .createQuest("Cross Brand", steps, false, null, new anchor.BN(0), new anchor.BN(100))
+        .accounts({
+          brand: brandPda,
+          quest: quest.publicKey,
+          reward: null,
+          authority: provider.wallet.publicKey,
+        })
+        .remainingAccounts(stepAccounts.map(pubkey => ({ pubkey, isSigner: false, isWritable: false })))
+        .signers([quest])
+        .rpc();
+    };
+
+    for (const [steps, stepAccounts] of [
+      [[ownStep, foreignStep], [ownStep, foreignStep]],
+      [[ownStep, foreignStep], [ownStep, ownStep]],
+      [[ownStep, foreignStep], [ownStep]],
+    ]) {
+      try {
+        await createQuest(steps, stepAccounts);
+        expect.fail("Expected an error but none was thrown");
+      } catch (error) {
+        expect(error.message).to.include("Error Code: InvalidQuestSteps");
+      }
+    }
+
+    await createQuest([ownStep], [ownStep]);
+  });
+});
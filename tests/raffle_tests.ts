@@ -0,0 +1,294 @@
+// tests/raffle_tests.ts
+import { expect } from 'chai';
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { VrfStub } from "../target/types/vrf_stub";
+import { program, provider, createUniqueBrand, initializeProgramState, TRONIC_ADMIN_PUBKEY, TRONIC_ADMIN_KEYPAIR, TOKEN_2022_PROGRAM_ID, getAssociatedTokenAddress } from './common';
+
+const vrfStub = anchor.workspace.VrfStub as Program<VrfStub>;
+
+describe("Raffle Tests", () => {
+  before(initializeProgramState);
+
+  it("Draws raffle winners from slot hashes or a VRF oracle and issues prizes", async () => {
+    const brandPda = await createUniqueBrand();
+
+    const prize = anchor.web3.Keypair.generate();
+    const prizeMint = anchor.web3.Keypair.generate();
+    await program.methods
+      .createFungibleReward("Raffle Tokens", "Tokens for raffle winners", new anchor.BN(1000))
+      .accounts({
+        brand: brandPda,
+        reward: prize.publicKey,
+        tokenMint: prizeMint.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([prize, prizeMint, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    const closesAt = new anchor.BN(Math.floor(Date.now() / 1000) + 4);
+    const createRaffle = async (name: string, oracle: anchor.web3.PublicKey | null) => {
+      const raffle = anchor.web3.Keypair.generate();
+      await program.methods
+        .createRaffle(name, { none: {} }, 10, new anchor.BN(25), 1, closesAt, oracle)
+        .accounts({
+          brand: brandPda,
+          raffle: raffle.publicKey,
+          prize: prize.publicKey,
+          authority: provider.wallet.publicKey,
+        })
+        .signers([raffle])
+        .rpc();
+      return raffle.publicKey;
+    };
+    const slotHashRaffle = await createRaffle("Slot Hash Raffle", null);
+    const oracleRaffle = await createRaffle("Oracle Raffle", vrfStub.programId);
+
+    const users = [anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate()];
+    const enter = (raffle: anchor.web3.PublicKey, user: anchor.web3.Keypair) =>
+      program.methods
+        .enterRaffle()
+        .accounts({
+          raffle,
+          user: user.publicKey,
+        })
+        .signers([user])
+        .rpc();
+    for (const raffle of [slotHashRaffle, oracleRaffle]) {
+      for (const user of users) {
+        await enter(raffle, user);
+      }
+    }
+
+    try {
+      await enter(slotHashRaffle, users[0]);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: AlreadyEnteredRaffle");
+    }
+
+    try {
+      await program.methods.drawRaffle().accounts({ raffle: slotHashRaffle }).rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: RaffleNotClosed");
+    }
+
+    await new Promise(resolve => setTimeout(resolve, 5000));
+
+    // Raffles without an oracle draw with the hash of a slot picked after they close
+    const drawSlotHashRaffle = () => program.methods.drawRaffle().accounts({ raffle: slotHashRaffle }).rpc();
+    try {
+      await drawSlotHashRaffle();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: RaffleDrawNotReady");
+    }
+
+    await program.methods.scheduleRaffleDraw().accounts({ raffle: slotHashRaffle }).rpc();
+    const scheduled = await program.account.raffle.fetch(slotHashRaffle);
+    try {
+      await program.methods.scheduleRaffleDraw().accounts({ raffle: slotHashRaffle }).rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: RaffleDrawAlreadyScheduled");
+    }
+    try {
+      await program.methods.scheduleRaffleDraw().accounts({ raffle: oracleRaffle }).rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: InvalidRandomness");
+    }
+
+    while (await provider.connection.getSlot() <= scheduled.drawSlot.toNumber()) {
+      await new Promise(resolve => setTimeout(resolve, 400));
+    }
+    await drawSlotHashRaffle();
+    const slotHashResult = await program.account.raffle.fetch(slotHashRaffle);
+    expect(slotHashResult.winners).to.have.lengthOf(1);
+
+    // The oracle raffle can't be drawn until the oracle has answered it
+    try {
+      await program.methods.drawRaffle().accounts({ raffle: oracleRaffle }).rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: InvalidRandomness");
+    }
+
+    await vrfStub.methods
+      .fulfill(oracleRaffle, Array.from(Buffer.alloc(32, 7)))
+      .accounts({
+        payer: provider.wallet.publicKey,
+      })
+      .rpc();
+    const [randomness] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("randomness"), oracleRaffle.toBuffer()],
+      vrfStub.programId
+    );
+    await program.methods
+      .drawRaffle()
+      .accounts({
+        raffle: oracleRaffle,
+        randomness,
+      })
+      .rpc();
+
+    const oracleResult = await program.account.raffle.fetch(oracleRaffle);
+    expect(oracleResult.winners).to.have.lengthOf(1);
+    const winner = oracleResult.winners[0].user;
+    expect(users.map(user => user.publicKey.toBase58())).to.include(winner.toBase58());
+
+    const winnerTokenAccount = anchor.utils.token.associatedAddress({
+      mint: prizeMint.publicKey,
+      owner: winner,
+    });
+    const issuePrize = (user: anchor.web3.PublicKey, userTokenAccount: anchor.web3.PublicKey) =>
+      program.methods
+        .issueRafflePrize()
+        .accounts({
+          brand: brandPda,
+          raffle: oracleRaffle,
+          tokenMint: prizeMint.publicKey,
+          winner: user,
+          winnerTokenAccount: userTokenAccount,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+
+    await issuePrize(winner, winnerTokenAccount);
+    const balance = await provider.connection.getTokenAccountBalance(winnerTokenAccount);
+    expect(balance.value.uiAmount).to.equal(25);
+
+    const loser = users.find(user => !user.publicKey.equals(winner)).publicKey;
+    try {
+      await issuePrize(loser, anchor.utils.token.associatedAddress({ mint: prizeMint.publicKey, owner: loser }));
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: NotRaffleWinner");
+    }
+  });
+
+  it("Only lets members of the required tier enter a membership raffle", async () => {
+    const brandPda = await createUniqueBrand();
+
+    const prize = anchor.web3.Keypair.generate();
+    const prizeMint = anchor.web3.Keypair.generate();
+    await program.methods
+      .createFungibleReward("Member Tokens", "Tokens for member raffle winners", new anchor.BN(1000))
+      .accounts({
+        brand: brandPda,
+        reward: prize.publicKey,
+        tokenMint: prizeMint.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([prize, prizeMint, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    const membershipData = anchor.web3.Keypair.generate();
+    await program.methods
+      .initializeMembership(new anchor.BN(1), "Raffle Club", "RAF", "https://example.com/", new anchor.BN(1000), true, 5)
+      .accounts({
+        brand: brandPda,
+        membershipData: membershipData.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([membershipData, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+    for (const tierId of ["GOLD", "SILVER"]) {
+      await program.methods
+        .createMembershipTier(tierId, new anchor.BN(30 * 24 * 60 * 60), true, `${tierId}.json`, null, false)
+        .accounts({
+          membershipData: membershipData.publicKey,
+          tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        })
+        .rpc();
+    }
+
+    const mintMember = async (tierIndex: number) => {
+      const mint = anchor.web3.Keypair.generate();
+      const member = anchor.web3.Keypair.generate();
+      await program.methods
+        .mintMembership(tierIndex)
+        .accountsPartial({
+          membershipData: membershipData.publicKey,
+          mint: mint.publicKey,
+          recipient: member.publicKey,
+          metadata: null,
+          masterEdition: null,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          tokenMetadataProgram: null,
+        })
+        .signers([mint])
+        .rpc();
+      const [membershipToken] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("membership-token"), mint.publicKey.toBuffer()],
+        program.programId
+      );
+      const memberTokenAccount = await getAssociatedTokenAddress(mint.publicKey, member.publicKey, false, TOKEN_2022_PROGRAM_ID);
+      return { member, membershipToken, memberTokenAccount };
+    };
+    const gold = await mintMember(0);
+    const silver = await mintMember(1);
+
+    const createRaffle = (membership: anchor.web3.PublicKey) => {
+      const raffle = anchor.web3.Keypair.generate();
+      return program.methods
+        .createRaffle(
+          "Gold Raffle",
+          { membership: { membership, tierId: "GOLD" } },
+          10,
+          new anchor.BN(25),
+          1,
+          new anchor.BN(Math.floor(Date.now() / 1000) + 60),
+          null
+        )
+        .accounts({
+          brand: brandPda,
+          raffle: raffle.publicKey,
+          prize: prize.publicKey,
+          authority: provider.wallet.publicKey,
+        })
+        .signers([raffle])
+        .rpc()
+        .then(() => raffle.publicKey);
+    };
+
+    // Raffles can only ask for one of the brand's own memberships
+    try {
+      await createRaffle(anchor.web3.Keypair.generate().publicKey);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: InvalidRaffle");
+    }
+    const raffle = await createRaffle(membershipData.publicKey);
+
+    const enter = (
+      holder: { member: anchor.web3.Keypair, membershipToken: anchor.web3.PublicKey, memberTokenAccount: anchor.web3.PublicKey },
+      user: anchor.web3.Keypair = holder.member
+    ) =>
+      program.methods
+        .enterRaffle()
+        .accounts({
+          raffle,
+          user: user.publicKey,
+          membershipToken: holder.membershipToken,
+          memberTokenAccount: holder.memberTokenAccount,
+        })
+        .signers([user])
+        .rpc();
+
+    // A member of another tier, or someone showing a member's token account, can't enter
+    for (const attempt of [() => enter(silver), () => enter(gold, silver.member)]) {
+      try {
+        await attempt();
+        expect.fail("Expected an error but none was thrown");
+      } catch (error) {
+        expect(error.message).to.include("Error Code: RaffleRequirementNotMet");
+      }
+    }
+
+    await enter(gold);
+    const raffleAccount = await program.account.raffle.fetch(raffle);
+    expect(raffleAccount.entries.map(entry => entry.toBase58())).to.deep.equal([gold.member.publicKey.toBase58()]);
+  });
+});
@@ -0,0 +1,129 @@
+// tests/referral_tests.ts
+import { expect } from 'chai';
+import * as anchor from "@coral-xyz/anchor";
+import { program, provider, createUniqueBrand, initializeProgramState, fundAccount, TOKEN_2022_PROGRAM_ID, TRONIC_ADMIN_PUBKEY, TRONIC_ADMIN_KEYPAIR } from './common';
+
+describe("Referral Tests", () => {
+  before(initializeProgramState);
+
+  it("Records referrers on membership mint and credits them up to the cap", async () => {
+    const brandPda = await createUniqueBrand();
+    const referrer = anchor.web3.Keypair.generate();
+    await fundAccount(provider.connection, referrer.publicKey);
+
+    await program.methods
+      .configureReferrals(new anchor.BN(30), new anchor.BN(0), 1)
+      .accounts({
+        brand: brandPda,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    await program.methods
+      .registerReferralCode("ALICE")
+      .accounts({
+        brand: brandPda,
+        referrer: referrer.publicKey,
+      })
+      .signers([referrer])
+      .rpc();
+    const [referralCode] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("referral-code"), brandPda.toBuffer(), Buffer.from("ALICE")],
+      program.programId
+    );
+
+    const membershipData = anchor.web3.Keypair.generate();
+    await program.methods
+      .initializeMembership(new anchor.BN(1), "Referral Membership", "REF", "https://example.com/", new anchor.BN(1000), true, 5)
+      .accounts({
+        brand: brandPda,
+        membershipData: membershipData.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([membershipData, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+    await program.methods
+      .createMembershipTier("BASIC", new anchor.BN(30 * 24 * 60 * 60), true, "basic.json", null, false)
+      .accounts({
+        membershipData: membershipData.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+      })
+      .rpc();
+
+    const findReferral = (member: anchor.web3.PublicKey) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("referral"), brandPda.toBuffer(), member.toBuffer()],
+        program.programId
+      )[0];
+    const mintReferred = (member: anchor.web3.PublicKey) => {
+      const mint = anchor.web3.Keypair.generate();
+      return program.methods
+        .mintMembership(0)
+        .accountsPartial({
+          membershipData: membershipData.publicKey,
+          mint: mint.publicKey,
+          recipient: member,
+          metadata: null,
+          masterEdition: null,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          tokenMetadataProgram: null,
+          referralCode,
+          referral: findReferral(member),
+        })
+        .signers([mint])
+        .rpc();
+    };
+
+    try {
+      await mintReferred(referrer.publicKey);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: SelfReferral");
+    }
+
+    const members = [anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate()];
+    for (const member of members) {
+      await mintReferred(member.publicKey);
+    }
+
+    const code = await program.account.referralCode.fetch(referralCode);
+    expect(code.referredCount).to.equal(2);
+    const referral = await program.account.referral.fetch(findReferral(members[0].publicKey));
+    expect(referral.referrer.toBase58()).to.equal(referrer.publicKey.toBase58());
+
+    const creditReferral = (member: anchor.web3.PublicKey) =>
+      program.methods
+        .creditReferral()
+        .accounts({
+          brand: brandPda,
+          referral: findReferral(member),
+          referralCode,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+
+    await creditReferral(members[0].publicKey);
+
+    const [referrerPoints] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user-points"), brandPda.toBuffer(), referrer.publicKey.toBuffer()],
+      program.programId
+    );
+    const points = await program.account.userPoints.fetch(referrerPoints);
+    expect(points.balance.toNumber()).to.equal(30);
+
+    try {
+      await creditReferral(members[0].publicKey);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: ReferralAlreadyCredited");
+    }
+
+    // The program credits each referrer for a single referral
+    try {
+      await creditReferral(members[1].publicKey);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: ReferralCapReached");
+    }
+  });
+});
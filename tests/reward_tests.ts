@@ -3,7 +3,7 @@ import { Program } from "@coral-xyz/anchor";
 import { CommunityEngagementProtocol } from "../target/types/community_engagement_protocol";
 import { expect } from 'chai';
 
-import { program, provider, brandList, createUniqueBrand, initializeProgramState, log, fundAccount, TRONIC_ADMIN_PUBKEY, TRONIC_ADMIN_KEYPAIR } from './common';
+import { program, provider, brandList, createUniqueBrand, initializeProgramState, log, fundAccount, TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TRONIC_ADMIN_PUBKEY, TRONIC_ADMIN_KEYPAIR } from './common';
 
 type RewardType = {
     fungible?: {
@@ -24,6 +24,9 @@ type RewardAccount = {
     createdAt: anchor.BN;
     updatedAt: anchor.BN;
     issuedCount: anchor.BN;
+    active: boolean;
+    startsAt: anchor.BN | null;
+    endsAt: anchor.BN | null;
   };
   
 
@@ -82,13 +85,15 @@ describe("Reward Tests", () => {
       .createNonFungibleReward(
         "Test Non-Fungible Reward",
         "A test non-fungible reward",
-        "https://example.com/metadata.json"
+        "https://example.com/metadata.json",
+        null
       )
       .accounts({
         brand: brandPda,
         reward: reward.publicKey,
         tokenMint: tokenMint.publicKey,
         tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([reward, tokenMint, TRONIC_ADMIN_KEYPAIR])
       .rpc();
@@ -178,13 +183,15 @@ describe("Reward Tests", () => {
       .createNonFungibleReward(
         "Test Non-Fungible Reward",
         "A test non-fungible reward",
-        "https://example.com/metadata.json"
+        "https://example.com/metadata.json",
+        null
       )
       .accounts({
         brand: brandPda,
         reward: reward.publicKey,
         tokenMint: tokenMint.publicKey,
         tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([reward, tokenMint, TRONIC_ADMIN_KEYPAIR])
       .rpc();
@@ -204,6 +211,7 @@ describe("Reward Tests", () => {
         user: user.publicKey,
         tronicAdmin: TRONIC_ADMIN_PUBKEY,
         tokenMint: tokenMint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([rewardInstance, TRONIC_ADMIN_KEYPAIR])
       .rpc();
@@ -257,12 +265,13 @@ describe("Reward Tests", () => {
 
     try {
       await program.methods
-        .createNonFungibleReward("Test NFT Reward", "A test NFT reward", "https://example.com/metadata.json")
+        .createNonFungibleReward("Test NFT Reward", "A test NFT reward", "https://example.com/metadata.json", null)
         .accounts({
           brand: brandPda,
           reward: reward.publicKey,
           tokenMint: tokenMint.publicKey,
           tronicAdmin: nonAdminKeypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([reward, tokenMint, nonAdminKeypair])
         .rpc();
@@ -315,4 +324,189 @@ describe("Reward Tests", () => {
       expect(error.message).to.include("Error Code: UnauthorizedTronicAdmin");
     }
   });
-});
\ No newline at end of file
+
+  it("Rejects issuing an inactive or expired reward", async () => {
+    const reward = anchor.web3.Keypair.generate();
+    const tokenMint = anchor.web3.Keypair.generate();
+    const user = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .createFungibleReward("Campaign Reward", "A limited-time reward", new anchor.BN(1000))
+      .accounts({
+        brand: brandPda,
+        reward: reward.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([reward, tokenMint, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    const issue = () =>
+      program.methods
+        .issueFungibleReward(new anchor.BN(1))
+        .accounts({
+          brand: brandPda,
+          reward: reward.publicKey,
+          user: user.publicKey,
+          tronicAdmin: TRONIC_ADMIN_PUBKEY,
+          tokenMint: tokenMint.publicKey,
+        })
+        .signers([TRONIC_ADMIN_KEYPAIR])
+        .rpc();
+
+    await program.methods
+      .setRewardActive(false)
+      .accounts({
+        brand: brandPda,
+        reward: reward.publicKey,
+        authority: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    try {
+      await issue();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: RewardInactive");
+    }
+
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods
+      .setRewardActive(true)
+      .accounts({
+        brand: brandPda,
+        reward: reward.publicKey,
+        authority: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+    await program.methods
+      .setRewardSchedule(new anchor.BN(now - 7200), new anchor.BN(now - 3600))
+      .accounts({
+        brand: brandPda,
+        reward: reward.publicKey,
+        authority: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    try {
+      await issue();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: RewardEnded");
+    }
+
+    const rewardAccount = await program.account.reward.fetch(reward.publicKey) as RewardAccount;
+    expect(rewardAccount.active).to.be.true;
+    expect(rewardAccount.endsAt.toNumber()).to.equal(now - 3600);
+  });
+
+  it("Batch issues a fungible reward and rejects totals above the remaining supply", async () => {
+    const reward = anchor.web3.Keypair.generate();
+    const tokenMint = anchor.web3.Keypair.generate();
+    const users = [anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate()];
+
+    await program.methods
+      .createFungibleReward("Batch Reward", "A batch-issued reward", new anchor.BN(500))
+      .accounts({
+        brand: brandPda,
+        reward: reward.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+      })
+      .signers([reward, tokenMint, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    const userTokenAccounts = users.map((user) =>
+      anchor.utils.token.associatedAddress({ mint: tokenMint.publicKey, owner: user.publicKey })
+    );
+    const recipients = users.flatMap((user, i) => [
+      { pubkey: user.publicKey, isSigner: false, isWritable: false },
+      { pubkey: userTokenAccounts[i], isSigner: false, isWritable: true },
+    ]);
+    const batchIssue = (amounts: number[]) =>
+      program.methods
+        .batchIssueFungibleReward(amounts.map((amount) => new anchor.BN(amount)))
+        .accounts({
+          brand: brandPda,
+          reward: reward.publicKey,
+          tronicAdmin: TRONIC_ADMIN_PUBKEY,
+          tokenMint: tokenMint.publicKey,
+        })
+        .remainingAccounts(recipients)
+        .signers([TRONIC_ADMIN_KEYPAIR])
+        .rpc();
+
+    await batchIssue([100, 250]);
+
+    const balances = await Promise.all(
+      userTokenAccounts.map((account) => provider.connection.getTokenAccountBalance(account))
+    );
+    expect(balances.map((balance) => balance.value.uiAmount)).to.deep.equal([100, 250]);
+    let rewardAccount = await program.account.reward.fetch(reward.publicKey) as RewardAccount;
+    expect(rewardAccount.rewardType.fungible.tokenSupply.toNumber()).to.equal(150);
+
+    try {
+      await batchIssue([100, 51]);
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: InsufficientRewardSupply");
+    }
+
+    const first = await provider.connection.getTokenAccountBalance(userTokenAccounts[0]);
+    expect(first.value.uiAmount).to.equal(100);
+    rewardAccount = await program.account.reward.fetch(reward.publicKey) as RewardAccount;
+    expect(rewardAccount.rewardType.fungible.tokenSupply.toNumber()).to.equal(150);
+  });
+
+  it("Only updates the description of Token-2022 rewards", async () => {
+    const reward = anchor.web3.Keypair.generate();
+    const tokenMint = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .createNonFungibleReward(
+        "Token-2022 Reward",
+        "A reward with on-mint metadata",
+        "https://example.com/metadata.json",
+        { symbol: "T22", uri: "https://example.com/metadata.json" }
+      )
+      .accounts({
+        brand: brandPda,
+        reward: reward.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([reward, tokenMint, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    const update = (name: string, metadataUri: string | null) =>
+      program.methods
+        .updateReward(name, "An updated description", metadataUri)
+        .accounts({
+          brand: brandPda,
+          reward: reward.publicKey,
+          tokenMint: tokenMint.publicKey,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+
+    for (const [name, metadataUri] of [
+      ["Renamed Reward", null],
+      ["Token-2022 Reward", "https://example.com/updated.json"],
+    ] as [string, string | null][]) {
+      try {
+        await update(name, metadataUri);
+        expect.fail("Expected an error but none was thrown");
+      } catch (error) {
+        expect(error.message).to.include("Error Code: RewardMetadataOnMint");
+      }
+    }
+
+    await update("Token-2022 Reward", null);
+    const rewardAccount = await program.account.reward.fetch(reward.publicKey) as RewardAccount;
+    expect(rewardAccount.description).to.equal("An updated description");
+  });
+});
@@ -0,0 +1,120 @@
+// tests/season_tests.ts
+import { expect } from 'chai';
+import * as anchor from "@coral-xyz/anchor";
+import { program, provider, createUniqueBrand, initializeProgramState, TOKEN_PROGRAM_ID, TRONIC_ADMIN_PUBKEY, TRONIC_ADMIN_KEYPAIR } from './common';
+
+describe("Season Tests", () => {
+  before(initializeProgramState);
+
+  it("Credits award points to the active season and snapshots it on close", async () => {
+    const brandPda = await createUniqueBrand();
+    const user = anchor.web3.Keypair.generate();
+    const userAchievements = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .initializeUserAchievements()
+      .accounts({
+        userAchievements: userAchievements.publicKey,
+        user: user.publicKey,
+        authority: provider.wallet.publicKey,
+      })
+      .signers([userAchievements, user])
+      .rpc();
+
+    const achievement = anchor.web3.Keypair.generate();
+    const tokenMint = anchor.web3.Keypair.generate();
+    await program.methods
+      .createFungibleAchievement("Checkin", "Season Checkin", "Check in during the season", 25, new anchor.BN(1000), false, null)
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([achievement, tokenMint, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    const seasonId = 1;
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods
+      .createSeason(seasonId, new anchor.BN(now - 60), new anchor.BN(now + 5))
+      .accounts({
+        brand: brandPda,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    const seasonIdBytes = Buffer.alloc(4);
+    seasonIdBytes.writeUInt32LE(seasonId);
+    const [season] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("season"), brandPda.toBuffer(), seasonIdBytes],
+      program.programId
+    );
+    const [userSeasonPoints] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user-season-points"), season.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .awardFungibleAchievement()
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        user: user.publicKey,
+        userAchievements: userAchievements.publicKey,
+        authority: provider.wallet.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        season,
+        userSeasonPoints,
+      })
+      .rpc();
+
+    const [userPoints] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user-points"), brandPda.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    const points = await program.account.userPoints.fetch(userPoints);
+    expect(points.balance.toNumber()).to.equal(25);
+
+    const seasonPoints = await program.account.userSeasonPoints.fetch(userSeasonPoints);
+    expect(seasonPoints.points.toNumber()).to.equal(25);
+
+    let seasonAccount = await program.account.season.fetch(season);
+    expect(seasonAccount.standings).to.have.lengthOf(1);
+    expect(seasonAccount.standings[0].user.toBase58()).to.equal(user.publicKey.toBase58());
+
+    const closeSeason = () =>
+      program.methods
+        .closeSeason([])
+        .accounts({
+          brand: brandPda,
+          season,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+
+    try {
+      await closeSeason();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: SeasonNotEnded");
+    }
+
+    await new Promise(resolve => setTimeout(resolve, 6000));
+    await closeSeason();
+
+    seasonAccount = await program.account.season.fetch(season);
+    expect(seasonAccount.closed).to.be.true;
+
+    const [seasonResult] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("season-result"), season.toBuffer()],
+      program.programId
+    );
+    const result = await program.account.seasonResult.fetch(seasonResult);
+    expect(result.seasonId).to.equal(seasonId);
+    expect(result.rewarded).to.equal(0);
+    expect(result.standings[0].points.toNumber()).to.equal(25);
+  });
+});
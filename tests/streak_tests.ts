@@ -0,0 +1,106 @@
+// tests/streak_tests.ts
+import { expect } from 'chai';
+import * as anchor from "@coral-xyz/anchor";
+import { program, provider, createUniqueBrand, initializeProgramState, TOKEN_PROGRAM_ID, TRONIC_ADMIN_PUBKEY, TRONIC_ADMIN_KEYPAIR } from './common';
+
+describe("Streak Tests", () => {
+  before(initializeProgramState);
+
+  it("Counts one activity per period and awards milestone achievements", async () => {
+    const brandPda = await createUniqueBrand();
+    const achievement = anchor.web3.Keypair.generate();
+    const tokenMint = anchor.web3.Keypair.generate();
+    const user = anchor.web3.Keypair.generate();
+    const userAchievements = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .createFungibleAchievement("First Day", "Started a daily streak", "1 day streak", 5, new anchor.BN(1000), false, null)
+      .accounts({
+        brand: brandPda,
+        achievement: achievement.publicKey,
+        tokenMint: tokenMint.publicKey,
+        tronicAdmin: TRONIC_ADMIN_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([achievement, tokenMint, TRONIC_ADMIN_KEYPAIR])
+      .rpc();
+
+    await program.methods
+      .initializeUserAchievements()
+      .accounts({
+        userAchievements: userAchievements.publicKey,
+        user: user.publicKey,
+        authority: provider.wallet.publicKey,
+      })
+      .signers([userAchievements, user])
+      .rpc();
+
+    const streak = anchor.web3.Keypair.generate();
+    await program.methods
+      .createStreak("Daily Check-in", new anchor.BN(86400), new anchor.BN(3600), [
+        { length: 1, achievement: achievement.publicKey },
+      ])
+      .accounts({
+        brand: brandPda,
+        streak: streak.publicKey,
+        authority: provider.wallet.publicKey,
+      })
+      .signers([streak])
+      .rpc();
+
+    // Reaching a milestone needs the milestone's award accounts
+    try {
+      await program.methods
+        .recordStreakActivity()
+        .accounts({
+          brand: brandPda,
+          streak: streak.publicKey,
+          user: user.publicKey,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+      expect.fail("Expected an error but none was thrown");
+    } catch (error) {
+      expect(error.message).to.include("Error Code: MilestoneAccountsRequired");
+    }
+
+    const [userAchievement] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user-achievement"), achievement.publicKey.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    const userTokenAccount = anchor.utils.token.associatedAddress({
+      mint: tokenMint.publicKey,
+      owner: user.publicKey,
+    });
+    const recordActivity = () =>
+      program.methods
+        .recordStreakActivity()
+        .accounts({
+          brand: brandPda,
+          streak: streak.publicKey,
+          user: user.publicKey,
+          authority: provider.wallet.publicKey,
+          milestoneAchievement: achievement.publicKey,
+          userAchievements: userAchievements.publicKey,
+          userAchievement,
+          tokenMint: tokenMint.publicKey,
+          userTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+    await recordActivity();
+    const balance = await provider.connection.getTokenAccountBalance(userTokenAccount);
+    expect(balance.value.uiAmount).to.equal(1);
+
+    // A second activity in the same period doesn't extend the streak
+    await recordActivity();
+    const [userStreakPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user-streak"), streak.publicKey.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    const userStreak = await program.account.userStreak.fetch(userStreakPda);
+    expect(userStreak.current).to.equal(1);
+    expect(userStreak.longest).to.equal(1);
+  });
+});